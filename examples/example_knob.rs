@@ -1,5 +1,7 @@
 use eframe::egui;
-use egui_fancy_knob::{Knob, KnobStyle, LabelPosition, add_knob};
+use egui_fancy_knob::{
+    Knob, KnobBank, KnobBankLayout, KnobStyle, KnobVisuals, LabelPosition, RingColor, add_knob,
+};
 
 fn main() -> eframe::Result<()> {
     eframe::run_native(
@@ -19,6 +21,8 @@ struct KnobExample {
     blue_value: f32,
     log_value: f32,
     neg_log_value: f32,
+    gradient_value: f32,
+    bank_values: Vec<f32>,
 }
 
 impl Default for KnobExample {
@@ -33,6 +37,8 @@ impl Default for KnobExample {
             blue_value: 0.0,
             log_value: 0.0,
             neg_log_value: 0.0,
+            gradient_value: 0.0,
+            bank_values: vec![0.5; 64],
         }
     }
 }
@@ -67,12 +73,6 @@ impl eframe::App for KnobExample {
                 ui.add(
                     Knob::new(21.6, |_| {}, 0.0..=100.0, KnobStyle::Wiper)
                         .with_label("Disabled", LabelPosition::Bottom)
-                        .with_colors(
-                            egui::Color32::DARK_GRAY,
-                            egui::Color32::GRAY,
-                            egui::Color32::GRAY,
-                            egui::Color32::GRAY,
-                        )
                         .with_size(50.0)
                         .with_font_size(14.0)
                         .with_stroke_width(3.0)
@@ -211,7 +211,36 @@ impl eframe::App for KnobExample {
                     .smallest_finite(1e-2)
                     .with_neutral(1.5),
                 );
+
+                ui.add(
+                    Knob::new(
+                        self.gradient_value,
+                        |value| self.gradient_value = value,
+                        0.0..=100.0,
+                        KnobStyle::Wiper,
+                    )
+                    .with_label("Gradient", LabelPosition::Bottom)
+                    .with_size(50.0)
+                    .with_stroke_width(3.0)
+                    .with_ring_color(RingColor::Gradient(
+                        egui::Color32::from_rgb(50, 120, 220),
+                        egui::Color32::from_rgb(220, 60, 60),
+                    ))
+                    .with_frame(egui::Frame::group(ui.style())),
+                );
             });
+
+            ui.separator();
+
+            let changed = KnobBank::show(
+                ui,
+                &mut self.bank_values,
+                &KnobBankLayout::default(),
+                &KnobVisuals::default(),
+            );
+            for index in changed {
+                println!("Bank knob {index} = {:.2}", self.bank_values[index]);
+            }
         });
     }
 }
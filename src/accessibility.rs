@@ -0,0 +1,85 @@
+use egui::{Id, Ui};
+use std::ops::RangeInclusive;
+
+/// Requests from assistive technology to change a knob's value.
+#[derive(Default)]
+pub(crate) struct AccessRequests {
+    /// Number of increments minus number of decrements.
+    pub steps: i32,
+    /// An exact value to set.
+    pub set_value: Option<f32>,
+}
+
+/// Collects this frame's AccessKit action requests for the knob with `id`.
+#[cfg(feature = "accesskit")]
+pub(crate) fn requests(ui: &Ui, id: Id) -> AccessRequests {
+    use egui::accesskit::{Action, ActionData};
+    ui.input(|input| {
+        let increments = input.num_accesskit_action_requests(id, Action::Increment) as i32;
+        let decrements = input.num_accesskit_action_requests(id, Action::Decrement) as i32;
+        let set_value = input
+            .accesskit_action_requests(id, Action::SetValue)
+            .filter_map(|request| match request.data {
+                Some(ActionData::NumericValue(value)) => Some(value as f32),
+                _ => None,
+            })
+            .last();
+        AccessRequests {
+            steps: increments - decrements,
+            set_value,
+        }
+    })
+}
+
+#[cfg(not(feature = "accesskit"))]
+pub(crate) fn requests(_ui: &Ui, _id: Id) -> AccessRequests {
+    AccessRequests::default()
+}
+
+/// Describes the knob with `id` to AccessKit as a slider over `range`.
+///
+/// When `announce` is set the value was just changed other than by dragging, e.g. by a reset or
+/// an assistive technology action, and screen readers are asked to read it out.
+#[cfg(feature = "accesskit")]
+pub(crate) fn describe(
+    ui: &Ui,
+    id: Id,
+    range: RangeInclusive<f32>,
+    step: Option<f32>,
+    value: f32,
+    announce: bool,
+) {
+    use egui::accesskit::{Action, Live};
+    ui.ctx().accesskit_node_builder(id, |builder| {
+        if announce {
+            builder.set_live(Live::Polite);
+        }
+        let (min, max) = (
+            range.start().min(*range.end()),
+            range.start().max(*range.end()),
+        );
+        builder.set_min_numeric_value(min as f64);
+        builder.set_max_numeric_value(max as f64);
+        if let Some(step) = step {
+            builder.set_numeric_value_step(step as f64);
+        }
+        builder.add_action(Action::SetValue);
+        if value < max {
+            builder.add_action(Action::Increment);
+        }
+        if value > min {
+            builder.add_action(Action::Decrement);
+        }
+    });
+}
+
+#[cfg(not(feature = "accesskit"))]
+pub(crate) fn describe(
+    _ui: &Ui,
+    _id: Id,
+    _range: RangeInclusive<f32>,
+    _step: Option<f32>,
+    _value: f32,
+    _announce: bool,
+) {
+}
@@ -0,0 +1,397 @@
+use egui::{Context, Id, Ui, lerp};
+use std::time::Duration;
+
+/// Shape of an animated transition over time.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    /// Constant speed.
+    Linear,
+    /// Starts fast and slows down towards the end.
+    EaseOutCubic,
+    /// A damped spring of unit mass, which may overshoot before settling.
+    ///
+    /// Time is measured in transition durations, so `stiffness: 100.0, damping: 10.0` has mostly
+    /// settled by the end of the transition. Lower damping gives more bounce, and higher damping
+    /// a slower start.
+    Spring { stiffness: f32, damping: f32 },
+}
+
+impl Easing {
+    /// Maps linear progress `t` in `0.0..=1.0` to eased progress.
+    ///
+    /// The result is `0.0` at the start and `1.0` at the end, but springs may go past `1.0` in
+    /// between.
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Easing::Spring { .. } if t >= 1.0 => 1.0,
+            Easing::Spring { stiffness, damping } => spring(stiffness, damping, t),
+        }
+    }
+}
+
+/// Position at time `t` of a unit mass spring released from `0.0` towards `1.0`.
+fn spring(stiffness: f32, damping: f32, t: f32) -> f32 {
+    let omega = stiffness.max(f32::EPSILON).sqrt();
+    let zeta = damping.max(0.0) / (2.0 * omega);
+    if zeta < 1.0 {
+        // Underdamped: oscillates around the target.
+        let decay = (-zeta * omega * t).exp();
+        let omega_d = omega * (1.0 - zeta * zeta).sqrt();
+        1.0 - decay * ((omega_d * t).cos() + zeta * omega / omega_d * (omega_d * t).sin())
+    } else {
+        // Critically damped or overdamped: creeps towards the target without reaching it, so
+        // it's stretched to arrive at the end of the transition.
+        creep(omega, zeta, t) / creep(omega, zeta, 1.0)
+    }
+}
+
+/// Position at time `t` of a spring with damping ratio `zeta` of at least `1.0`.
+fn creep(omega: f32, zeta: f32, t: f32) -> f32 {
+    let root = (zeta * zeta - 1.0).sqrt();
+    if root < 1e-3 {
+        // Critically damped.
+        return 1.0 - (-omega * t).exp() * (1.0 + omega * t);
+    }
+    // Overdamped: the sum of a slow and a fast decaying exponential. The slow rate is written
+    // as `1 / (zeta + root)` rather than `zeta - root`, which cancels out for large `zeta`.
+    let slow = -omega / (zeta + root);
+    let fast = -omega * (zeta + root);
+    let slow_weight = (zeta + root) / (2.0 * root);
+    1.0 - (slow_weight * (slow * t).exp() + (1.0 - slow_weight) * (fast * t).exp())
+}
+
+/// Durations and easing for every animated transition of the knob.
+///
+/// Set a duration to [`Duration::ZERO`] to make that transition instant.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Easing, KnobAnimations};
+/// # use std::time::Duration;
+/// let animations = KnobAnimations {
+///     smoothing: Duration::from_millis(100),
+///     easing: Easing::Spring {
+///         stiffness: 150.0,
+///         damping: 12.0,
+///     },
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KnobAnimations {
+    /// Highlighting the knob when the pointer hovers over it.
+    pub hover: Duration,
+    /// Growing the knob when a drag starts, and shrinking it again when released.
+    pub drag_pop: Duration,
+    /// Sweeping the indicator back to neutral on double click.
+    pub reset: Duration,
+    /// Moving the indicator to a value set from outside the knob, e.g. by automation.
+    /// The true value is always reported immediately; only the display is smoothed.
+    pub smoothing: Duration,
+    /// Fading out the highlight shown when the value is changed from outside the knob.
+    pub flash: Duration,
+    /// Easing applied to all of the above.
+    pub easing: Easing,
+}
+
+impl KnobAnimations {
+    /// All transitions are instant.
+    pub const NONE: Self = Self {
+        hover: Duration::ZERO,
+        drag_pop: Duration::ZERO,
+        reset: Duration::ZERO,
+        smoothing: Duration::ZERO,
+        flash: Duration::ZERO,
+        easing: Easing::Linear,
+    };
+}
+
+impl KnobAnimations {
+    /// The animations to actually run in `ui`.
+    ///
+    /// Everything is instant when reduced motion is on, or when the style disables animation.
+    pub(crate) fn effective(self, ui: &Ui) -> Self {
+        if !motion_allowed(ui) {
+            Self {
+                easing: self.easing,
+                ..Self::NONE
+            }
+        } else {
+            self
+        }
+    }
+}
+
+impl Default for KnobAnimations {
+    fn default() -> Self {
+        Self {
+            hover: Duration::from_millis(100),
+            drag_pop: Duration::from_millis(80),
+            reset: Duration::from_millis(150),
+            smoothing: Duration::ZERO,
+            flash: Duration::from_millis(400),
+            easing: Easing::EaseOutCubic,
+        }
+    }
+}
+
+/// Time between frames of the knob's own animations.
+pub(crate) const ANIMATION_FRAME: Duration = Duration::from_millis(16);
+
+/// Time between frames of slow ambient animations, like the MIDI-learn pulse.
+pub(crate) const AMBIENT_FRAME: Duration = Duration::from_millis(33);
+
+/// Asks for another frame in `interval`, because something is still animating.
+///
+/// Knobs only ever call this while a transition is running, so a static panel of knobs doesn't
+/// keep repainting.
+pub(crate) fn request_animation_frame(ctx: &Context, interval: Duration) {
+    ctx.request_repaint_after(interval);
+}
+
+fn reduced_motion_id() -> Id {
+    Id::new("egui_fancy_knob::reduced_motion")
+}
+
+/// Turns off every knob animation in `ctx`, e.g. to honour an operating system reduced-motion
+/// preference.
+///
+/// Knobs also skip animation when [`egui::Style::animation_time`] is zero.
+pub fn set_reduced_motion(ctx: &Context, reduced_motion: bool) {
+    ctx.data_mut(|data| data.insert_temp(reduced_motion_id(), reduced_motion));
+}
+
+/// Whether reduced motion has been turned on with [`set_reduced_motion`].
+pub fn reduced_motion(ctx: &Context) -> bool {
+    ctx.data(|data| data.get_temp(reduced_motion_id()))
+        .unwrap_or(false)
+}
+
+/// Whether knobs in `ui` may animate at all.
+pub(crate) fn motion_allowed(ui: &Ui) -> bool {
+    !reduced_motion(ui.ctx()) && ui.style().animation_time > 0.0
+}
+
+/// Eased progress of a transition towards `value`, from `0.0` (off) to `1.0` (on).
+pub(crate) fn animate_bool(
+    ctx: &Context,
+    id: Id,
+    value: bool,
+    duration: Duration,
+    easing: Easing,
+) -> f32 {
+    easing.apply(ctx.animate_bool_with_time(id, value, duration.as_secs_f32()))
+}
+
+/// Strength of a flash that fades out over `duration`, from `1.0` when `trigger` is set down to
+/// `0.0`. `start_time` holds when the flash was last triggered.
+pub(crate) fn flash(
+    ctx: &Context,
+    start_time: &mut Option<f64>,
+    trigger: bool,
+    duration: Duration,
+    easing: Easing,
+) -> f32 {
+    let time = ctx.input(|input| input.time);
+    if trigger {
+        *start_time = Some(time);
+    }
+    let Some(start) = *start_time else {
+        return 0.0;
+    };
+    let elapsed = (time - start) as f32;
+    if elapsed >= duration.as_secs_f32() {
+        *start_time = None;
+        return 0.0;
+    }
+    request_animation_frame(ctx, ANIMATION_FRAME);
+    1.0 - easing.apply(elapsed / duration.as_secs_f32())
+}
+
+/// Transition of the displayed knob position between two normalised values.
+#[derive(Clone, Copy)]
+pub(crate) struct Sweep {
+    from: f32,
+    to: f32,
+    start_time: f64,
+    duration: f32,
+}
+
+impl Sweep {
+    /// A sweep from `from` to `to` starting now.
+    pub(crate) fn new(ctx: &Context, from: f32, to: f32, duration: Duration) -> Self {
+        Self {
+            from,
+            to,
+            start_time: ctx.input(|input| input.time),
+            duration: duration.as_secs_f32(),
+        }
+    }
+
+    fn position(&self, time: f64, easing: Easing) -> f32 {
+        if self.duration <= 0.0 {
+            return self.to;
+        }
+        let t = (time - self.start_time) as f32 / self.duration;
+        lerp(self.from..=self.to, easing.apply(t))
+    }
+
+    fn is_finished(&self, time: f64) -> bool {
+        (time - self.start_time) as f32 >= self.duration
+    }
+}
+
+/// The normalised position to display for a knob whose actual position is `target`.
+///
+/// Follows the current `sweep`, if it's heading for `target`. When `target` changes to something
+/// else, the display moves towards it over `smoothing`.
+pub(crate) fn displayed_position(
+    ctx: &Context,
+    sweep: &mut Option<Sweep>,
+    target: f32,
+    smoothing: Duration,
+    easing: Easing,
+) -> f32 {
+    let time = ctx.input(|input| input.time);
+    let current = match *sweep {
+        Some(current) if current.to == target => current,
+        previous => {
+            let from = previous.map_or(target, |previous| previous.position(time, easing));
+            *sweep.insert(Sweep::new(ctx, from, target, smoothing))
+        }
+    };
+
+    if current.is_finished(time) {
+        target
+    } else {
+        request_animation_frame(ctx, ANIMATION_FRAME);
+        current.position(time, easing)
+    }
+}
+
+/// A reset sweep that reports its intermediate values, rather than only moving the display.
+#[derive(Clone, Copy)]
+pub(crate) struct ValueSweep {
+    sweep: Sweep,
+    /// The exact value to report once the sweep has finished.
+    value: f32,
+}
+
+impl ValueSweep {
+    /// A sweep from normalised position `from` to `to` starting now, finishing at `value`.
+    pub(crate) fn new(ctx: &Context, from: f32, to: f32, value: f32, duration: Duration) -> Self {
+        Self {
+            sweep: Sweep::new(ctx, from, to, duration),
+            value,
+        }
+    }
+}
+
+/// Progress of a [`ValueSweep`].
+pub(crate) enum ValueSweepStep {
+    /// The sweep is at this normalised position.
+    Moving(f32),
+    /// The sweep has finished on this value.
+    Finished(f32),
+}
+
+/// Advances the value sweep, if there is one, and clears it once finished.
+pub(crate) fn step_value_sweep(
+    ctx: &Context,
+    value_sweep: &mut Option<ValueSweep>,
+    easing: Easing,
+) -> Option<ValueSweepStep> {
+    let ValueSweep { sweep, value } = (*value_sweep)?;
+    let time = ctx.input(|input| input.time);
+    if sweep.is_finished(time) {
+        *value_sweep = None;
+        Some(ValueSweepStep::Finished(value))
+    } else {
+        request_animation_frame(ctx, ANIMATION_FRAME);
+        Some(ValueSweepStep::Moving(sweep.position(time, easing)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPRINGS: [Easing; 3] = [
+        // Underdamped, critically damped and overdamped.
+        Easing::Spring {
+            stiffness: 100.0,
+            damping: 10.0,
+        },
+        Easing::Spring {
+            stiffness: 100.0,
+            damping: 20.0,
+        },
+        Easing::Spring {
+            stiffness: 100.0,
+            damping: 40.0,
+        },
+    ];
+
+    #[test]
+    fn springs_start_and_end_in_place() {
+        for easing in SPRINGS {
+            assert!(easing.apply(0.0).abs() < 1e-6, "{easing:?}");
+            assert_eq!(easing.apply(1.0), 1.0, "{easing:?}");
+            assert_eq!(easing.apply(2.0), 1.0, "{easing:?}");
+        }
+    }
+
+    #[test]
+    fn underdamped_springs_overshoot() {
+        let peak = (0..100)
+            .map(|i| SPRINGS[0].apply(i as f32 / 100.0))
+            .fold(0.0, f32::max);
+        assert!(peak > 1.0);
+    }
+
+    #[test]
+    fn damped_springs_approach_without_overshooting() {
+        for easing in &SPRINGS[1..] {
+            let mut previous = 0.0;
+            for i in 0..100 {
+                let eased = easing.apply(i as f32 / 100.0);
+                assert!(eased >= previous && eased <= 1.0, "{easing:?} at {i}");
+                previous = eased;
+            }
+        }
+    }
+
+    #[test]
+    fn heavily_damped_springs_stay_finite_and_monotone() {
+        for damping in [150.0, 200.0, 400.0, 10_000.0] {
+            let easing = Easing::Spring {
+                stiffness: 100.0,
+                damping,
+            };
+            let mut previous = 0.0;
+            for i in 0..=100 {
+                let eased = easing.apply(i as f32 / 100.0);
+                assert!(eased.is_finite(), "{easing:?} at {i}");
+                assert!(eased >= previous && eased <= 1.0, "{easing:?} at {i}");
+                previous = eased;
+            }
+        }
+    }
+
+    #[test]
+    fn heavily_damped_springs_arrive_without_jumping() {
+        let easing = Easing::Spring {
+            stiffness: 100.0,
+            damping: 150.0,
+        };
+        assert!(easing.apply(0.99) > 0.95);
+    }
+
+    #[test]
+    fn springs_mostly_settle() {
+        assert!((SPRINGS[0].apply(0.99) - 1.0).abs() < 0.01);
+    }
+}
@@ -0,0 +1,298 @@
+use crate::normalise::snap_normalised;
+use crate::{KNOB_FINE_DRAG_RATIO, KNOB_MOTION, KnobStyle, needle_shapes, start_turn};
+use egui::{Color32, FontId, Galley, Id, Pos2, Rect, Sense, Shape, Stroke, TextStyle, Ui, Vec2};
+use std::collections::hash_map::DefaultHasher;
+use std::f32::consts::TAU;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Colors and style shared by every knob in a [`KnobBank`], or by any number of knobs through
+/// [`crate::Knob::with_visuals`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobVisuals {
+    pub style: KnobStyle,
+    pub stroke_width: f32,
+    pub knob_color: Color32,
+    pub knob_dragging_color: Color32,
+    pub line_color: Color32,
+    /// Space above and below each knob, see [`crate::Knob::with_margin`]. In a [`KnobBank`] it
+    /// goes above each knob and below its label.
+    pub margin: f32,
+    /// Space around each label's text, see [`crate::Knob::with_label_padding`].
+    pub label_padding: f32,
+}
+
+impl Default for KnobVisuals {
+    fn default() -> Self {
+        Self {
+            style: KnobStyle::Wiper,
+            stroke_width: 2.0,
+            knob_color: Color32::GRAY,
+            knob_dragging_color: Color32::WHITE,
+            line_color: Color32::GRAY,
+            margin: 4.0,
+            label_padding: 2.0,
+        }
+    }
+}
+
+/// Grid arrangement of a [`KnobBank`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobBankLayout {
+    /// Number of knobs per row
+    pub columns: usize,
+    /// Diameter of each knob
+    pub knob_size: f32,
+    /// Space between neighbouring knobs
+    pub spacing: Vec2,
+    /// Step the knobs snap to while dragged, in normalised units, e.g. `0.25` for five
+    /// positions. `None` moves them smoothly.
+    pub step: Option<f32>,
+    /// Label under each knob, by index. Knobs past the end of the list have none, and without
+    /// any labels no space is made for them.
+    pub labels: Vec<String>,
+}
+
+impl Default for KnobBankLayout {
+    fn default() -> Self {
+        Self {
+            columns: 16,
+            knob_size: 24.0,
+            spacing: Vec2::splat(4.0),
+            step: None,
+            labels: Vec::new(),
+        }
+    }
+}
+
+/// The drag of one knob in a [`KnobBank`].
+#[derive(Clone, Copy)]
+struct BankDrag {
+    index: usize,
+    /// Normalised value when the drag started.
+    start: f32,
+    /// Vertical drag distance since the drag started, after fine-drag scaling.
+    distance: f32,
+}
+
+/// The label galleys last laid out for a [`KnobBank`], with a hash of everything they were
+/// laid out from.
+#[derive(Clone)]
+struct CachedLabels {
+    key: u64,
+    galleys: Arc<[Arc<Galley>]>,
+}
+
+/// The galleys for `labels`, reused from the previous frame of the bank with `id` when neither
+/// the labels nor their font and color have changed.
+fn cached_labels(
+    ui: &Ui,
+    id: Id,
+    labels: &[String],
+    font_id: &FontId,
+    color: Color32,
+) -> Arc<[Arc<Galley>]> {
+    let mut hasher = DefaultHasher::new();
+    labels.hash(&mut hasher);
+    font_id.hash(&mut hasher);
+    color.hash(&mut hasher);
+    let key = hasher.finish();
+    if let Some(cached) = ui.data(|data| data.get_temp::<CachedLabels>(id))
+        && cached.key == key
+    {
+        return cached.galleys;
+    }
+    let galleys: Arc<[_]> = ui.fonts(|fonts| {
+        labels
+            .iter()
+            .map(|label| fonts.layout_no_wrap(label.clone(), font_id.clone(), color))
+            .collect()
+    });
+    ui.data_mut(|data| {
+        data.insert_temp(
+            id,
+            CachedLabels {
+                key,
+                galleys: galleys.clone(),
+            },
+        )
+    });
+    galleys
+}
+
+/// A grid of many knobs, drawn and interacted with as a single widget.
+///
+/// Use this instead of [`crate::Knob`] for banks of hundreds of knobs: the whole grid is one
+/// interaction, its shapes are collected into one list and painted together, and labels are
+/// laid out once and reused until they change. Only [`KnobStyle::Needle`] allocates per knob,
+/// for the outline of each needle.
+pub struct KnobBank;
+
+impl KnobBank {
+    /// Shows a knob for every value in `values`, which are normalised to `0.0..=1.0`.
+    ///
+    /// Returns the indices of the values that were changed this frame.
+    pub fn show(
+        ui: &mut Ui,
+        values: &mut [f32],
+        layout: &KnobBankLayout,
+        visuals: &KnobVisuals,
+    ) -> Vec<usize> {
+        let count = values.len();
+        let columns = layout.columns.max(1);
+        let rows = count.div_ceil(columns);
+        let font_id = TextStyle::Small.resolve(ui.style());
+        let margin = visuals.margin.max(0.0);
+        let label_padding = visuals.label_padding.max(0.0);
+        let label_height = if layout.labels.is_empty() {
+            0.0
+        } else {
+            ui.fonts(|fonts| fonts.row_height(&font_id)) + label_padding * 2.0
+        };
+        let cell = Vec2::new(
+            layout.knob_size,
+            layout.knob_size + label_height + margin * 2.0,
+        ) + layout.spacing;
+        let size = Vec2::new(cell.x * columns.min(count) as f32, cell.y * rows as f32);
+        let (rect, response) = ui.allocate_exact_size(size, Sense::click_and_drag());
+
+        // Index of the knob under `pos`, if any.
+        let knob_at = |pos: Pos2| {
+            let local = pos - rect.min;
+            let column = (local.x / cell.x).floor();
+            let row = (local.y / cell.y).floor();
+            if column < 0.0 || row < 0.0 || column as usize >= columns {
+                return None;
+            }
+            let index = row as usize * columns + column as usize;
+            (index < count).then_some(index)
+        };
+
+        let drag_id = response.id.with("active");
+        if response.drag_started() {
+            let drag = response
+                .interact_pointer_pos()
+                .and_then(knob_at)
+                .map(|index| BankDrag {
+                    index,
+                    start: values[index],
+                    distance: 0.0,
+                });
+            ui.data_mut(|data| data.insert_temp(drag_id, drag));
+        }
+        let drag = if response.dragged() {
+            ui.data(|data| data.get_temp::<Option<BankDrag>>(drag_id))
+                .flatten()
+        } else {
+            None
+        };
+        let active = drag.map(|drag| drag.index);
+
+        let mut changed = Vec::new();
+        if let Some(mut drag) = drag {
+            let mut delta = response.drag_delta().y;
+            ui.input(|input| {
+                if input.modifiers.ctrl || input.modifiers.shift || input.modifiers.alt {
+                    delta *= KNOB_FINE_DRAG_RATIO;
+                }
+            });
+            drag.distance += delta;
+            ui.data_mut(|data| data.insert_temp(drag_id, Some(drag)));
+
+            // Measured from the start of the drag, like a knob's, so small movements add up to
+            // a step.
+            let step = layout.step.unwrap_or(0.005);
+            let mut new_value = (drag.start - drag.distance * step).clamp(0.0, 1.0);
+            if layout.step.is_some() {
+                new_value = snap_normalised(new_value, step);
+            }
+            if new_value != values[drag.index] {
+                values[drag.index] = new_value;
+                changed.push(drag.index);
+            }
+        }
+
+        if !ui.is_rect_visible(rect) {
+            return changed;
+        }
+
+        let labels = if layout.labels.is_empty() {
+            Arc::from([])
+        } else {
+            let color = ui.visuals().text_color();
+            cached_labels(
+                ui,
+                response.id.with("labels"),
+                &layout.labels,
+                &font_id,
+                color,
+            )
+        };
+        let hovered = response.hover_pos().and_then(knob_at);
+        let clip = ui.clip_rect();
+        let radius = layout.knob_size * 0.5;
+        let start = start_turn(KNOB_MOTION);
+        let mut shapes = Vec::with_capacity(values.len() * 3 + labels.len());
+        for (index, value) in values.iter().enumerate() {
+            let min = rect.min
+                + Vec2::new(
+                    cell.x * (index % columns) as f32,
+                    cell.y * (index / columns) as f32 + margin,
+                );
+            let knob_rect = Rect::from_min_size(min, Vec2::splat(layout.knob_size));
+            // The label hangs below the knob, so check the whole cell.
+            let cell_rect = Rect::from_min_size(min - Vec2::new(0.0, margin), cell);
+            if !clip.intersects(cell_rect) {
+                continue;
+            }
+
+            let center = knob_rect.center();
+            let knob_color = if active == Some(index) {
+                visuals.knob_dragging_color
+            } else if hovered == Some(index) {
+                visuals
+                    .knob_color
+                    .lerp_to_gamma(visuals.knob_dragging_color, 0.5)
+            } else {
+                visuals.knob_color
+            };
+            shapes.push(Shape::circle_stroke(
+                center,
+                radius,
+                Stroke::new(visuals.stroke_width, knob_color),
+            ));
+
+            if let Some(galley) = labels.get(index) {
+                let pos = egui::pos2(
+                    center.x - galley.size().x * 0.5,
+                    knob_rect.bottom() + label_padding,
+                );
+                shapes.push(Shape::galley(pos, galley.clone(), Color32::PLACEHOLDER));
+            }
+
+            let angle = TAU * (value.clamp(0.0, 1.0) * KNOB_MOTION + start);
+            let pointer = center + Vec2::angled(angle) * (radius * 0.7);
+            match visuals.style {
+                KnobStyle::Wiper => shapes.push(Shape::line_segment(
+                    [center, pointer],
+                    Stroke::new(visuals.stroke_width * 1.5, visuals.line_color),
+                )),
+                KnobStyle::Dot => shapes.push(Shape::circle_filled(
+                    pointer,
+                    visuals.stroke_width * 1.5,
+                    visuals.line_color,
+                )),
+                KnobStyle::Needle => shapes.extend(needle_shapes(
+                    center,
+                    angle,
+                    radius * 0.85,
+                    visuals.stroke_width,
+                    visuals.line_color,
+                )),
+            }
+        }
+        ui.painter().extend(shapes);
+
+        changed
+    }
+}
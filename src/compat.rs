@@ -0,0 +1,44 @@
+//! The egui APIs that have changed shape between recent egui releases, behind one small
+//! interface.
+//!
+//! Only egui 0.31 is supported. The rest of the crate calls these instead of egui directly,
+//! but it also uses APIs older releases lack entirely, such as pixel rounding and focus lock
+//! filters, so this is not a multi-version layer.
+
+use egui::{Id, Painter, PopupCloseBehavior, Rect, Response, Stroke, StrokeKind, Ui};
+
+/// Strokes the inside edge of `rect`. Before egui 0.31 strokes were always centered on the
+/// edge, and `rect` would need shrinking by half the stroke width.
+pub(crate) fn rect_stroke_inside(painter: &Painter, rect: Rect, stroke: Stroke) {
+    painter.rect_stroke(rect, 0.0, stroke, StrokeKind::Inside);
+}
+
+/// Strokes the outside edge of `rect`, see [`rect_stroke_inside`].
+pub(crate) fn rect_stroke_outside(painter: &Painter, rect: Rect, stroke: Stroke) {
+    painter.rect_stroke(rect, 0.0, stroke, StrokeKind::Outside);
+}
+
+/// Closes the context menu `ui` is in.
+pub(crate) fn close_menu(ui: &mut Ui) {
+    ui.close_menu();
+}
+
+/// Shows the popup `popup_id` below `response` while it's open, closing it on any click.
+/// Clicking `response` opens or closes it.
+pub(crate) fn popup_below<R>(
+    ui: &Ui,
+    popup_id: Id,
+    response: &Response,
+    add_contents: impl FnOnce(&mut Ui) -> R,
+) -> Option<R> {
+    if response.clicked() {
+        ui.memory_mut(|memory| memory.toggle_popup(popup_id));
+    }
+    egui::popup_below_widget(
+        ui,
+        popup_id,
+        response,
+        PopupCloseBehavior::CloseOnClick,
+        add_contents,
+    )
+}
@@ -0,0 +1,33 @@
+use egui::{Pos2, Rect};
+
+/// Points sampled from a curve function across the knob.
+const CURVE_SAMPLES: usize = 32;
+
+/// A curve drawn inside the knob, see [`crate::Knob::with_curve`].
+pub(crate) enum Curve<'a> {
+    /// `y = f(t)` for `t` from `0.0` to `1.0`
+    Function(Box<dyn Fn(f32) -> f32 + 'a>),
+    /// `[x, y]` points, each from `0.0` to `1.0`
+    Points(&'a [[f32; 2]]),
+}
+
+impl Curve<'_> {
+    /// The curve's points in `rect`, with `y = 0.0` at the bottom.
+    pub(crate) fn points_in(&self, rect: Rect) -> Vec<Pos2> {
+        let to_screen = |x: f32, y: f32| {
+            Pos2::new(
+                egui::lerp(rect.x_range(), x.clamp(0.0, 1.0)),
+                egui::lerp(rect.bottom()..=rect.top(), y.clamp(0.0, 1.0)),
+            )
+        };
+        match self {
+            Curve::Function(f) => (0..=CURVE_SAMPLES)
+                .map(|i| {
+                    let t = i as f32 / CURVE_SAMPLES as f32;
+                    to_screen(t, f(t))
+                })
+                .collect(),
+            Curve::Points(points) => points.iter().map(|&[x, y]| to_screen(x, y)).collect(),
+        }
+    }
+}
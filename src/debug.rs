@@ -0,0 +1,47 @@
+use crate::compat;
+use egui::{Align2, Color32, Context, FontId, Id, Painter, Rect, Stroke};
+
+fn debug_overlay_id() -> Id {
+    Id::new("egui_fancy_knob::debug_overlay")
+}
+
+/// Draws the debug overlay on every knob in `ctx`, as if each had [`crate::Knob::debug`] set.
+pub fn set_debug_overlay(ctx: &Context, debug_overlay: bool) {
+    ctx.data_mut(|data| data.insert_temp(debug_overlay_id(), debug_overlay));
+}
+
+/// Whether the debug overlay has been turned on with [`set_debug_overlay`].
+pub fn debug_overlay(ctx: &Context) -> bool {
+    ctx.data(|data| data.get_temp(debug_overlay_id()))
+        .unwrap_or(false)
+}
+
+/// Geometry and state of a knob, drawn by the debug overlay.
+pub(crate) struct DebugInfo {
+    /// Area that responds to the pointer.
+    pub hit_rect: Rect,
+    /// Area of the knob and its label.
+    pub rect: Rect,
+    /// Area of the knob itself.
+    pub knob_rect: Rect,
+    pub normalised: f32,
+    /// Angle of the indicator, in radians.
+    pub angle: f32,
+}
+
+pub(crate) fn paint_debug_overlay(painter: &Painter, info: &DebugInfo) {
+    compat::rect_stroke_outside(painter, info.hit_rect, Stroke::new(1.0, Color32::BLUE));
+    compat::rect_stroke_inside(painter, info.rect, Stroke::new(1.0, Color32::RED));
+    compat::rect_stroke_inside(painter, info.knob_rect, Stroke::new(1.0, Color32::GREEN));
+    painter.text(
+        info.knob_rect.left_top(),
+        Align2::LEFT_TOP,
+        format!(
+            "{:.3}\n{:.0}°",
+            info.normalised,
+            info.angle.to_degrees().rem_euclid(360.0)
+        ),
+        FontId::monospace(8.0),
+        Color32::YELLOW,
+    );
+}
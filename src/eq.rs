@@ -0,0 +1,186 @@
+use crate::{Knob, KnobStyle, LabelPosition};
+use egui::{Color32, Response, RichText, Shape, Ui, Widget};
+
+type OnChange<'a> = Box<dyn FnMut(EqBand) + 'a>;
+
+/// The settings of one equalizer band, as edited by [`EqBandKnobs`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EqBand {
+    /// Center frequency in Hz
+    pub frequency: f32,
+    /// Gain in dB
+    pub gain: f32,
+    /// Quality factor: higher is narrower
+    pub q: f32,
+    pub enabled: bool,
+}
+
+impl Default for EqBand {
+    fn default() -> Self {
+        Self {
+            frequency: 1000.0,
+            gain: 0.0,
+            q: std::f32::consts::FRAC_1_SQRT_2,
+            enabled: true,
+        }
+    }
+}
+
+/// Frequency, gain and Q knobs for one equalizer band, with a toggle to enable the band.
+///
+/// The knobs share the band's color, and hovering any of them highlights the whole band. Changes
+/// are reported once per frame with the band's new settings. Double clicking a knob resets it
+/// to the default band.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{EqBand, EqBandKnobs};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut band = EqBand::default();
+/// ui.add(
+///     EqBandKnobs::new("Mid", band, |new_band| band = new_band)
+///         .with_color(egui::Color32::from_rgb(90, 170, 250)),
+/// );
+/// # });
+/// # });
+/// ```
+pub struct EqBandKnobs<'a> {
+    name: &'a str,
+    band: EqBand,
+    on_change: OnChange<'a>,
+    color: Color32,
+    size: f32,
+}
+
+impl<'a> EqBandKnobs<'a> {
+    pub fn new(name: &'a str, band: EqBand, on_change: impl FnMut(EqBand) + 'a) -> Self {
+        Self {
+            name,
+            band,
+            on_change: Box::new(on_change),
+            color: Color32::GRAY,
+            size: 36.0,
+        }
+    }
+
+    /// Sets the band's color, used for all three knobs and the highlight.
+    pub fn with_color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Sets the size of each knob.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+/// `hz` with a unit, e.g. `"1.20 kHz"`.
+fn frequency_text(hz: f32) -> String {
+    if hz >= 1000.0 {
+        format!("{:.2} kHz", hz / 1000.0)
+    } else {
+        format!("{hz:.0} Hz")
+    }
+}
+
+/// Applies the look shared by the knobs of a band.
+fn band_knob<'k, F: FnMut(f32)>(
+    knob: Knob<'k, F>,
+    label: &'static str,
+    band: &EqBandKnobs<'_>,
+    text_color: Color32,
+    enabled: bool,
+) -> Knob<'k, F> {
+    knob.with_size(band.size)
+        .with_colors(band.color, Color32::WHITE, band.color, text_color)
+        .with_label(label, LabelPosition::Bottom)
+        .enabled(enabled)
+}
+
+impl Widget for EqBandKnobs<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let mut band = self.band;
+        let defaults = EqBand::default();
+        let color = self.color;
+        let text_color = ui.visuals().text_color();
+        // Filled in once we know whether any part of the band is hovered.
+        let highlight = ui.painter().add(Shape::Noop);
+
+        let mut response = ui
+            .vertical(|ui| {
+                let mut response = ui.checkbox(
+                    &mut band.enabled,
+                    RichText::new(self.name).color(color).strong(),
+                );
+                ui.horizontal(|ui| {
+                    let mut frequency = band.frequency;
+                    let mut gain = band.gain;
+                    let mut q = band.q;
+                    response |= ui.add(
+                        band_knob(
+                            Knob::new(
+                                frequency,
+                                |v| frequency = v,
+                                20.0..=20_000.0,
+                                KnobStyle::Wiper,
+                            ),
+                            "Freq",
+                            &self,
+                            text_color,
+                            band.enabled,
+                        )
+                        .logarithmic(true)
+                        .with_neutral(defaults.frequency)
+                        .with_label_format(frequency_text),
+                    );
+                    response |= ui.add(
+                        band_knob(
+                            Knob::new(gain, |v| gain = v, -24.0..=24.0, KnobStyle::Wiper),
+                            "Gain",
+                            &self,
+                            text_color,
+                            band.enabled,
+                        )
+                        .with_neutral(defaults.gain)
+                        .with_label_format(|db| format!("{db:+.1} dB")),
+                    );
+                    response |= ui.add(
+                        band_knob(
+                            Knob::new(q, |v| q = v, 0.1..=18.0, KnobStyle::Wiper),
+                            "Q",
+                            &self,
+                            text_color,
+                            band.enabled,
+                        )
+                        .logarithmic(true)
+                        .with_neutral(defaults.q)
+                        .with_label_format(|q| format!("{q:.2}")),
+                    );
+                    band.frequency = frequency;
+                    band.gain = gain;
+                    band.q = q;
+                });
+                response
+            })
+            .inner;
+
+        let band_rect = response.rect.expand(ui.spacing().item_spacing.x * 0.5);
+        if ui.rect_contains_pointer(band_rect) {
+            ui.painter().set(
+                highlight,
+                Shape::rect_filled(band_rect, 4.0, color.gamma_multiply(0.15)),
+            );
+        }
+
+        if band != self.band {
+            (self.on_change)(band);
+            response.mark_changed();
+        }
+        response
+    }
+}
@@ -0,0 +1,19 @@
+/// Something that happened to a knob this frame, see [`crate::KnobResponse::events`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum KnobEvent {
+    /// The user started dragging the knob at `value`
+    DragStarted { value: f32 },
+    /// The value was changed by the knob and reported to the setter
+    ValueChanged { old: f32, new: f32 },
+    /// The knob was double clicked to reset it to its neutral value
+    ResetToNeutral,
+    /// The user released the knob at `value`
+    DragEnded { value: f32 },
+    /// The user tried to change a read-only knob, e.g. so the host can explain why it's locked
+    EditBlocked,
+    /// A value was typed into the value entry and confirmed. It is clamped to the range before
+    /// being set
+    EditSubmitted { value: f32 },
+    /// A value was pasted onto the knob. It is clamped to the range before being set
+    Pasted { value: f32 },
+}
@@ -0,0 +1,77 @@
+use std::ops::RangeInclusive;
+
+/// Nominal f-numbers in 1/3 stops, from f/1 to f/32.
+const APERTURES: [&str; 31] = [
+    "1", "1.1", "1.2", "1.4", "1.6", "1.8", "2", "2.2", "2.5", "2.8", "3.2", "3.5", "4", "4.5",
+    "5", "5.6", "6.3", "7.1", "8", "9", "10", "11", "13", "14", "16", "18", "20", "22", "25", "29",
+    "32",
+];
+
+/// Nominal shutter speeds in 1/3 stops, from 1/8000 s to 30 s.
+const SHUTTER_SPEEDS: [&str; 55] = [
+    "1/8000", "1/6400", "1/5000", "1/4000", "1/3200", "1/2500", "1/2000", "1/1600", "1/1250",
+    "1/1000", "1/800", "1/640", "1/500", "1/400", "1/320", "1/250", "1/200", "1/160", "1/125",
+    "1/100", "1/80", "1/60", "1/50", "1/40", "1/30", "1/25", "1/20", "1/15", "1/13", "1/10", "1/8",
+    "1/6", "1/5", "1/4", "0.3", "0.4", "0.5", "0.6", "0.8", "1", "1.3", "1.6", "2", "2.5", "3.2",
+    "4", "5", "6", "8", "10", "13", "15", "20", "25", "30",
+];
+
+/// Index of 1 s in [`SHUTTER_SPEEDS`].
+const SHUTTER_ONE_SECOND: i32 = 39;
+
+/// What an exposure knob sets, see [`crate::Knob::exposure`].
+///
+/// Values are in stops, so the knob moves in even 1/3 EV steps whatever it shows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Exposure {
+    /// The aperture, in stops from f/1, shown as e.g. `"f/5.6"`
+    Aperture,
+    /// The shutter speed, in stops from 1 s (negative is faster), shown as e.g. `"1/250 s"`
+    Shutter,
+    /// Exposure compensation in EV, shown as e.g. `"+0.7 EV"`
+    Compensation,
+}
+
+impl Exposure {
+    /// The range of stops covered by the standard sequence: f/1 to f/32, 1/8000 s to 30 s, or
+    /// -3 to +3 EV.
+    pub fn range(self) -> RangeInclusive<f32> {
+        match self {
+            Exposure::Aperture => 0.0..=10.0,
+            Exposure::Shutter => -13.0..=5.0,
+            Exposure::Compensation => -3.0..=3.0,
+        }
+    }
+
+    /// The text for `stops`, using the nominal values photographers know, like f/5.6 rather
+    /// than f/5.66.
+    ///
+    /// ```
+    /// # use egui_fancy_knob::Exposure;
+    /// assert_eq!(Exposure::Aperture.format(5.0), "f/5.6");
+    /// assert_eq!(Exposure::Shutter.format(-8.0), "1/250 s");
+    /// assert_eq!(Exposure::Compensation.format(2.0 / 3.0), "+0.7 EV");
+    /// ```
+    pub fn format(self, stops: f32) -> String {
+        let third = (stops * 3.0).round() as i32;
+        match self {
+            Exposure::Aperture => match usize::try_from(third)
+                .ok()
+                .and_then(|index| APERTURES.get(index))
+            {
+                Some(aperture) => format!("f/{aperture}"),
+                None => format!("f/{:.1}", 2f32.powf(stops / 2.0)),
+            },
+            Exposure::Shutter => match usize::try_from(third + SHUTTER_ONE_SECOND)
+                .ok()
+                .and_then(|index| SHUTTER_SPEEDS.get(index))
+            {
+                Some(speed) => format!("{speed} s"),
+                None if stops < 0.0 => format!("1/{:.0} s", 2f32.powf(-stops)),
+                None => format!("{:.0} s", 2f32.powf(stops)),
+            },
+            Exposure::Compensation if third == 0 => "0.0 EV".to_owned(),
+            Exposure::Compensation => format!("{:+.1} EV", third as f32 / 3.0),
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::KnobEvent;
+use egui::{Pos2, Response, Vec2};
+use smallvec::SmallVec;
+use std::ops::Deref;
+
+/// Where a knob was drawn this frame, for painting overlays anchored to it, like modulation
+/// arrows or routing cables.
+///
+/// Angles are in radians, clockwise from pointing right.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KnobGeometry {
+    pub center: Pos2,
+    /// Radius of the ring, including the growth while dragging
+    pub radius: f32,
+    /// Angle of the indicator at the start of the range
+    pub min_angle: f32,
+    /// Angle of the indicator at the end of the range
+    pub max_angle: f32,
+    /// Angle of the indicator as currently displayed
+    pub angle: f32,
+}
+
+impl KnobGeometry {
+    /// The angle of the indicator at normalised position `normalised`.
+    pub fn angle_at(&self, normalised: f32) -> f32 {
+        egui::lerp(self.min_angle..=self.max_angle, normalised.clamp(0.0, 1.0))
+    }
+
+    /// The point `distance` from the center at `angle`. Use [`KnobGeometry::radius`] for points
+    /// on the ring.
+    pub fn point_at(&self, angle: f32, distance: f32) -> Pos2 {
+        self.center + Vec2::angled(angle) * distance
+    }
+
+    /// The tip of the indicator on the ring, in screen coordinates. Draw lines from here to
+    /// whatever the knob controls on screen, like an oscilloscope's trigger level, to keep the
+    /// two visually linked.
+    pub fn anchor(&self) -> Pos2 {
+        self.point_at(self.angle, self.radius)
+    }
+}
+
+/// What [`crate::Knob::show`] returns: the widget's [`Response`], where the knob was drawn, and
+/// how far it was dragged.
+pub struct KnobResponse {
+    pub response: Response,
+    /// `None` when the knob was shown as a drag field, see [`crate::Knob::with_fallback_width`].
+    pub geometry: Option<KnobGeometry>,
+    /// How much dragging the knob changed the value this frame, in value units.
+    pub drag_delta: f32,
+    /// How much the value has changed since the current drag started, in value units. Also set
+    /// on the frame the drag stops.
+    ///
+    /// Both deltas are zero when the knob isn't being dragged, and when shown as a drag field.
+    pub drag_total: f32,
+    /// Number of detents the drag moved across this frame: step boundaries, or the neutral
+    /// value for knobs without a step. Use it to play a click or trigger haptics, like a
+    /// detented encoder.
+    pub detents_crossed: u32,
+    /// Whether a drag pushed past the start of the range this frame, having been inside it.
+    /// Use it to play a thud or trigger haptics at the end stop.
+    pub hit_min: bool,
+    /// Whether a drag pushed past the end of the range this frame, having been inside it.
+    pub hit_max: bool,
+    /// What happened to the knob this frame, in order. A typical frame's events fit inline,
+    /// without allocating.
+    pub events: SmallVec<[KnobEvent; 4]>,
+    /// The `(time, value)` pairs a drag went through, from its start, on the frame it stops.
+    /// Times are egui's input time in seconds. Only recorded with
+    /// [`crate::Knob::record_gestures`].
+    pub gesture: Option<Vec<(f64, f32)>>,
+}
+
+impl KnobResponse {
+    /// A response with nothing to report beyond `response`, and no geometry.
+    pub(crate) fn new(response: Response) -> Self {
+        Self {
+            response,
+            geometry: None,
+            drag_delta: 0.0,
+            drag_total: 0.0,
+            detents_crossed: 0,
+            hit_min: false,
+            hit_max: false,
+            events: SmallVec::new(),
+            gesture: None,
+        }
+    }
+}
+
+impl Deref for KnobResponse {
+    type Target = Response;
+
+    fn deref(&self) -> &Response {
+        &self.response
+    }
+}
@@ -0,0 +1,239 @@
+use egui::{Context, Id, Modifiers};
+use std::time::Duration;
+
+use crate::state::{self, Queued};
+
+/// How the other knobs in a [`KnobGroup`] follow the dragged one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GroupMode {
+    /// Every knob's normalised position is scaled by the same factor as the dragged knob's
+    #[default]
+    Proportional,
+    /// Every knob moves by the same normalised distance as the dragged knob
+    Relative,
+}
+
+/// Knobs edited together: dragging one with the group's modifiers held moves the others too.
+///
+/// Knobs are identified by the id given to [`crate::Knob::with_id`], and join the group with
+/// [`crate::Knob::with_group`]. The other knobs move on their next frame, reporting their new
+/// values through their setters. While the group's modifiers are held, drags aren't fine.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{GroupMode, Knob, KnobGroup, KnobStyle};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut gains = [0.0_f32; 4];
+/// let gain_id = egui::Id::new("gain");
+/// let group = KnobGroup::new("eq gains", (0..gains.len()).map(|band| gain_id.with(band)))
+///     .with_mode(GroupMode::Relative);
+/// ui.horizontal(|ui| {
+///     for (band, gain) in gains.iter_mut().enumerate() {
+///         ui.add(
+///             Knob::new(*gain, |v| *gain = v, -12.0..=12.0, KnobStyle::Wiper)
+///                 .with_id(gain_id.with(band))
+///                 .with_group(&group),
+///         );
+///     }
+/// });
+/// for (id, normalised) in group.changes(ui.ctx()) {
+///     println!("{id:?} moved to {normalised}");
+/// }
+/// # });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct KnobGroup {
+    id: Id,
+    members: Vec<Id>,
+    modifiers: Modifiers,
+    mode: GroupMode,
+}
+
+/// Normalised positions of the knobs when a group drag started.
+#[derive(Clone, Default)]
+struct DragStart {
+    dragged: f32,
+    others: Vec<(Id, f32)>,
+}
+
+/// Normalised positions a group moved its knobs to, in one frame.
+#[derive(Clone, Default)]
+struct Changes {
+    pass_nr: u64,
+    positions: Vec<(Id, f32)>,
+}
+
+impl KnobGroup {
+    /// A group with a unique `id` and the ids of its knobs.
+    pub fn new<I: Into<Id>>(id: impl Into<Id>, members: impl IntoIterator<Item = I>) -> Self {
+        Self {
+            id: id.into(),
+            members: members.into_iter().map(Into::into).collect(),
+            modifiers: Modifiers::SHIFT,
+            mode: GroupMode::default(),
+        }
+    }
+
+    /// Sets the modifiers that must be held when a drag starts to move the whole group.
+    /// The default is shift.
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Sets how the other knobs follow the dragged one.
+    pub fn with_mode(mut self, mode: GroupMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Whether the knob with `id` is part of the group.
+    pub fn contains(&self, id: impl Into<Id>) -> bool {
+        self.members.contains(&id.into())
+    }
+
+    /// The knobs the group moved this frame and their new normalised values, including the
+    /// dragged knob. Call this after showing the knobs.
+    ///
+    /// The values are the ones the knobs took on, after snapping to their own steps. Knobs shown
+    /// before the dragged one take on their new value in the next frame, and are reported then.
+    pub fn changes(&self, ctx: &Context) -> Vec<(Id, f32)> {
+        ctx.data(|data| data.get_temp::<Changes>(self.changes_id()))
+            .filter(|changes| changes.pass_nr == ctx.cumulative_pass_nr())
+            .map(|changes| changes.positions)
+            .unwrap_or_default()
+    }
+
+    pub(crate) fn modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    fn start_id(&self) -> Id {
+        self.id.with("drag_start")
+    }
+
+    fn changes_id(&self) -> Id {
+        self.id.with("changes")
+    }
+
+    /// Remembers where the knobs are when `dragged` starts moving the group from normalised
+    /// `position`.
+    pub(crate) fn begin_drag(&self, ctx: &Context, dragged: Id, position: f32) {
+        let start = DragStart {
+            dragged: position,
+            others: self
+                .members
+                .iter()
+                .filter(|&&id| id != dragged)
+                .filter_map(|&id| Some((id, state::last_position(ctx, id)?)))
+                .collect(),
+        };
+        ctx.data_mut(|data| data.insert_temp(self.start_id(), start));
+    }
+
+    /// Moves the other knobs after `dragged` moved to normalised position `to`.
+    pub(crate) fn drag(&self, ctx: &Context, dragged: Id, to: f32) {
+        let start: DragStart = ctx
+            .data(|data| data.get_temp(self.start_id()))
+            .unwrap_or_default();
+        let from = start.dragged;
+        record_change(ctx, self.changes_id(), dragged, to);
+        for (id, position) in start.others {
+            let position = match self.mode {
+                GroupMode::Proportional if from > 0.0 => position * to / from,
+                // Nothing to scale from, so move by the same distance instead.
+                GroupMode::Proportional | GroupMode::Relative => position + to - from,
+            }
+            .clamp(0.0, 1.0);
+            state::queue(
+                ctx,
+                id,
+                Queued {
+                    normalised: position,
+                    morph: Duration::ZERO,
+                    group_changes: Some(self.changes_id()),
+                },
+            );
+        }
+    }
+
+    /// Forgets the drag started with [`KnobGroup::begin_drag`].
+    pub(crate) fn end_drag(&self, ctx: &Context) {
+        ctx.data_mut(|data| data.remove_temp::<DragStart>(self.start_id()));
+    }
+}
+
+/// Records that the knob with `id` moved to normalised `position` this frame, for
+/// [`KnobGroup::changes`] of the group with `changes_id`.
+pub(crate) fn record_change(ctx: &Context, changes_id: Id, id: Id, position: f32) {
+    let pass_nr = ctx.cumulative_pass_nr();
+    ctx.data_mut(|data| {
+        let changes = data.get_temp_mut_or_default::<Changes>(changes_id);
+        if changes.pass_nr != pass_nr {
+            *changes = Changes {
+                pass_nr,
+                positions: Vec::new(),
+            };
+        }
+        match changes
+            .positions
+            .iter_mut()
+            .find(|(changed, _)| *changed == id)
+        {
+            Some((_, changed)) => *changed = position,
+            None => changes.positions.push((id, position)),
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KnobHarness;
+    use crate::{Knob, KnobStyle};
+    use egui::{Response, Ui};
+    use std::collections::HashMap;
+
+    /// Shows a smooth knob and a stepped one, returning the smooth one, and keeps the latest
+    /// position the group reported for each.
+    fn show_group(
+        ui: &mut Ui,
+        values: &mut [f32; 2],
+        group: &KnobGroup,
+        reported: &mut HashMap<Id, f32>,
+    ) -> Response {
+        let [smooth, stepped] = values;
+        let response = ui.add(
+            Knob::new(*smooth, |v| *smooth = v, 0.0..=1.0, KnobStyle::Wiper)
+                .with_id("smooth")
+                .with_group(group),
+        );
+        ui.add(
+            Knob::new(*stepped, |v| *stepped = v, 0.0..=1.0, KnobStyle::Wiper)
+                .with_id("stepped")
+                .with_step(0.25)
+                .with_group(group),
+        );
+        reported.extend(group.changes(ui.ctx()));
+        response
+    }
+
+    #[test]
+    fn changes_match_what_the_knobs_hold() {
+        let group = KnobGroup::new("group", ["smooth", "stepped"]).with_mode(GroupMode::Relative);
+        let mut values = [0.2, 0.5];
+        let mut reported = HashMap::new();
+        let mut harness = KnobHarness::new().with_modifiers(Modifiers::SHIFT);
+        harness.drag(-40.0, |ui| {
+            show_group(ui, &mut values, &group, &mut reported)
+        });
+        harness.idle(2, |ui| show_group(ui, &mut values, &group, &mut reported));
+        assert!(values[0] > 0.2);
+        assert!(values[1] > 0.5 && values[1] % 0.25 == 0.0, "{values:?}");
+        assert_eq!(reported[&Id::new("smooth")], values[0]);
+        assert_eq!(reported[&Id::new("stepped")], values[1]);
+    }
+}
@@ -0,0 +1,68 @@
+use crate::{KnobGeometry, state};
+use egui::{Context, Id, Pos2};
+use std::time::Duration;
+
+/// Drives a knob from outside the UI, e.g. from OSC, the network or a hardware controller.
+///
+/// Values are given in the knob's normalised `0.0..=1.0` space. On its next frame the knob
+/// converts them through its range, taper and step, and reports the result through its setter
+/// like any other change. The knob must have a stable id from [`crate::Knob::with_id`].
+///
+/// Handles are cheap to clone and can be sent to other threads.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Knob, KnobHandle, KnobStyle};
+/// let ctx = egui::Context::default();
+/// let handle = KnobHandle::new(&ctx, "cutoff");
+/// std::thread::spawn(move || handle.set_normalised(0.5))
+///     .join()
+///     .unwrap();
+///
+/// let mut value = 0.0;
+/// let _ = ctx.run(Default::default(), |ctx| {
+///     egui::CentralPanel::default().show(ctx, |ui| {
+///         ui.add(Knob::new(value, |v| value = v, 0.0..=10.0, KnobStyle::Wiper).with_id("cutoff"));
+///     });
+/// });
+/// assert_eq!(value, 5.0);
+/// ```
+#[derive(Clone, Debug)]
+pub struct KnobHandle {
+    ctx: Context,
+    id: Id,
+}
+
+impl KnobHandle {
+    /// A handle to the knob with `id` in `ctx`.
+    pub fn new(ctx: &Context, id: impl Into<Id>) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            id: id.into(),
+        }
+    }
+
+    /// The id of the knob this handle drives.
+    pub fn id(&self) -> Id {
+        self.id
+    }
+
+    /// Moves the knob to `normalised`, clamped to `0.0..=1.0`, on its next frame.
+    ///
+    /// A repaint is requested, so the change is applied even if the UI is otherwise idle.
+    pub fn set_normalised(&self, normalised: f32) {
+        state::queue_normalised(&self.ctx, self.id, normalised, Duration::ZERO);
+    }
+
+    /// Where the knob was drawn on its last frame, or `None` if it hasn't been shown yet.
+    ///
+    /// Useful for painting overlays before the knob is shown this frame, e.g. in a plot above it.
+    pub fn geometry(&self) -> Option<KnobGeometry> {
+        state::last_geometry(&self.ctx, self.id)
+    }
+
+    /// The tip of the knob's indicator on its last frame, see [`KnobGeometry::anchor`].
+    pub fn anchor(&self) -> Option<Pos2> {
+        self.geometry().map(|geometry| geometry.anchor())
+    }
+}
@@ -0,0 +1,68 @@
+use egui::{Context, Id};
+
+fn current_id() -> Id {
+    Id::new("egui_fancy_knob::current_interaction")
+}
+
+/// The knob being dragged or edited, see [`KnobInteractions::current`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobInteraction {
+    /// The knob's id, as given to [`crate::Knob::with_id`] or chosen by egui
+    pub id: Id,
+    /// The knob's label, if it has one
+    pub label: Option<String>,
+    pub value: f32,
+    /// The label and value as the knob displays them, e.g. `"Cutoff: 1.2 kHz"`
+    pub text: String,
+    /// Whether a value is being typed in, rather than dragged
+    pub editing: bool,
+}
+
+/// The last interaction and the pass it was recorded in.
+#[derive(Clone)]
+struct Recorded {
+    pass: u64,
+    interaction: KnobInteraction,
+}
+
+/// Which knob the user is interacting with, across the whole [`Context`].
+///
+/// Use it for a status bar readout, or to ignore shortcuts that would conflict with a drag.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::KnobInteractions;
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// egui::TopBottomPanel::bottom("status").show(ctx, |ui| {
+///     if let Some(interaction) = KnobInteractions::current(ctx) {
+///         ui.label(interaction.text);
+///     }
+/// });
+/// # });
+/// ```
+pub struct KnobInteractions;
+
+impl KnobInteractions {
+    /// The knob being dragged or edited, if any.
+    ///
+    /// Knobs report themselves when they are shown, so this also covers knobs shown later in
+    /// the frame, as of the previous frame.
+    pub fn current(ctx: &Context) -> Option<KnobInteraction> {
+        let recorded = ctx.data(|data| data.get_temp::<Recorded>(current_id()))?;
+        (recorded.pass + 1 >= ctx.cumulative_pass_nr()).then_some(recorded.interaction)
+    }
+
+    /// Whether any knob is being dragged or edited.
+    pub fn is_active(ctx: &Context) -> bool {
+        Self::current(ctx).is_some()
+    }
+
+    pub(crate) fn record(ctx: &Context, interaction: KnobInteraction) {
+        let recorded = Recorded {
+            pass: ctx.cumulative_pass_nr(),
+            interaction,
+        };
+        ctx.data_mut(|data| data.insert_temp(current_id(), recorded));
+    }
+}
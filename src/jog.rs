@@ -0,0 +1,295 @@
+use egui::{Color32, Id, Response, Sense, Stroke, Ui, Vec2};
+use std::f32::consts::{PI, TAU};
+
+/// How far the shuttle ring turns each way from the center, in radians.
+const SHUTTLE_ANGLE: f32 = 0.75 * PI;
+/// Radius of the jog wheel, as a fraction of the whole widget's radius. The rest is the
+/// shuttle ring.
+const JOG_RADIUS: f32 = 0.65;
+
+/// Which part of a [`JogShuttle`] is being dragged.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Grab {
+    Jog,
+    Shuttle,
+}
+
+/// Interaction state of a [`JogShuttle`], kept between frames.
+#[derive(Clone, Copy, Debug, Default)]
+struct JogState {
+    grab: Option<Grab>,
+    /// Pointer angle in the previous frame of a jog drag
+    last_angle: f32,
+    /// Rotation not yet reported as a whole tick
+    remainder: f32,
+    /// Where the jog wheel's dimple points, for drawing
+    wheel_angle: f32,
+    /// Current shuttle speed
+    speed: f32,
+}
+
+/// What a [`JogShuttle`] did this frame.
+pub struct JogShuttleResponse {
+    pub response: Response,
+    /// Ticks the jog wheel was turned this frame, positive for clockwise. Step the playhead by
+    /// this many frames, samples, etc.
+    pub ticks: i32,
+    /// Playback speed from the shuttle ring, from `-max_speed` to `max_speed`. Zero unless the
+    /// ring is held.
+    pub speed: f32,
+}
+
+/// A jog/shuttle wheel for scrubbing a timeline, as in video and audio editors.
+///
+/// The inner jog wheel turns endlessly, reporting ticks as it's turned around its center. The
+/// outer shuttle ring sets a playback speed from how far it's turned from the top, and springs
+/// back to zero on release.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::JogShuttle;
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut playhead = 0i64;
+/// let jog = JogShuttle::new().with_size(120.0).show(ui);
+/// playhead += jog.ticks as i64;
+/// if jog.speed != 0.0 {
+///     // Play at `jog.speed` times normal speed.
+/// }
+/// # });
+/// # });
+/// ```
+pub struct JogShuttle {
+    size: f32,
+    ticks_per_turn: u32,
+    max_speed: f32,
+    color: Color32,
+    active_color: Color32,
+    id: Option<Id>,
+}
+
+impl Default for JogShuttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl JogShuttle {
+    pub fn new() -> Self {
+        Self {
+            size: 100.0,
+            ticks_per_turn: 32,
+            max_speed: 8.0,
+            color: Color32::GRAY,
+            active_color: Color32::WHITE,
+            id: None,
+        }
+    }
+
+    /// Sets the diameter of the whole wheel
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Sets how many ticks one full turn of the jog wheel reports. The default is 32.
+    pub fn with_ticks_per_turn(mut self, ticks: u32) -> Self {
+        self.ticks_per_turn = ticks.max(1);
+        self
+    }
+
+    /// Sets the speed with the shuttle ring turned all the way. The default is 8.0, and it must
+    /// be positive.
+    pub fn with_max_speed(mut self, speed: f32) -> Self {
+        self.max_speed = speed.max(f32::EPSILON);
+        self
+    }
+
+    /// Sets the color of the wheel, and the color of the part being turned.
+    pub fn with_colors(mut self, color: Color32, active_color: Color32) -> Self {
+        self.color = color;
+        self.active_color = active_color;
+        self
+    }
+
+    /// Sets the id the wheel's state is stored under.
+    pub fn with_id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn show(self, ui: &mut Ui) -> JogShuttleResponse {
+        let (rect, mut response) =
+            ui.allocate_exact_size(Vec2::splat(self.size), Sense::click_and_drag());
+        let id = self.id.unwrap_or(response.id).with("jog");
+        let mut state = ui
+            .data(|data| data.get_temp::<JogState>(id))
+            .unwrap_or_default();
+        let center = rect.center();
+        let radius = self.size * 0.5;
+
+        let pointer_angle = response
+            .interact_pointer_pos()
+            .map(|pointer| (pointer - center).angle());
+        if response.drag_started()
+            && let Some(pointer) = response.interact_pointer_pos()
+        {
+            state.grab = Some(if (pointer - center).length() < radius * JOG_RADIUS {
+                Grab::Jog
+            } else {
+                Grab::Shuttle
+            });
+            state.last_angle = pointer_angle.unwrap_or_default();
+            state.remainder = 0.0;
+        }
+        if !response.dragged() {
+            state.grab = None;
+        }
+
+        let mut ticks = 0;
+        match (state.grab, pointer_angle) {
+            (Some(Grab::Jog), Some(angle)) => {
+                // The shortest way round, so crossing from +π to -π isn't a full turn back.
+                let delta = (angle - state.last_angle + PI).rem_euclid(TAU) - PI;
+                state.last_angle = angle;
+                state.wheel_angle = (state.wheel_angle + delta).rem_euclid(TAU);
+                state.remainder += delta * self.ticks_per_turn as f32 / TAU;
+                ticks = state.remainder.trunc() as i32;
+                state.remainder -= ticks as f32;
+                state.speed = 0.0;
+            }
+            (Some(Grab::Shuttle), Some(angle)) => {
+                let deflection = ((angle + PI * 0.5 + PI).rem_euclid(TAU) - PI)
+                    .clamp(-SHUTTLE_ANGLE, SHUTTLE_ANGLE);
+                state.speed = deflection / SHUTTLE_ANGLE * self.max_speed;
+            }
+            _ => state.speed = 0.0,
+        }
+        if ticks != 0 || state.speed != 0.0 {
+            response.mark_changed();
+        }
+
+        if ui.is_rect_visible(rect) {
+            let painter = ui.painter();
+            let visuals = ui.style().interact(&response);
+            let color_for = |grab| {
+                if state.grab == Some(grab) {
+                    self.active_color
+                } else {
+                    self.color
+                }
+            };
+            let stroke_width = (self.size * 0.02).max(1.0);
+
+            // Shuttle ring, with a notch at the top and a marker at the current speed.
+            let ring_radius = radius * (1.0 + JOG_RADIUS) * 0.5;
+            let ring_width = radius * (1.0 - JOG_RADIUS) * 0.8;
+            let shuttle_color = color_for(Grab::Shuttle);
+            painter.circle_stroke(
+                center,
+                ring_radius,
+                Stroke::new(ring_width, shuttle_color.gamma_multiply(0.3)),
+            );
+            let top = center - Vec2::new(0.0, ring_radius);
+            painter.circle_filled(top, stroke_width, visuals.text_color());
+            let shuttle_angle = -PI * 0.5 + state.speed / self.max_speed * SHUTTLE_ANGLE;
+            painter.circle_filled(
+                center + Vec2::angled(shuttle_angle) * ring_radius,
+                ring_width * 0.35,
+                shuttle_color,
+            );
+
+            // Jog wheel, with a dimple that turns along with it.
+            let jog_radius = radius * JOG_RADIUS * 0.95;
+            let jog_color = color_for(Grab::Jog);
+            painter.circle(
+                center,
+                jog_radius,
+                jog_color.gamma_multiply(0.15),
+                Stroke::new(stroke_width, jog_color),
+            );
+            painter.circle_filled(
+                center + Vec2::angled(state.wheel_angle - PI * 0.5) * (jog_radius * 0.7),
+                jog_radius * 0.12,
+                jog_color,
+            );
+        }
+
+        let speed = state.speed;
+        ui.data_mut(|data| data.insert_temp(id, state));
+        JogShuttleResponse {
+            response,
+            ticks,
+            speed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KnobHarness;
+    use egui::{Event, Modifiers, PointerButton};
+
+    /// Shows a 100 point wheel, adding up its ticks and keeping its latest speed.
+    fn show_wheel(ui: &mut Ui, ticks: &mut i32, speed: &mut f32) -> Response {
+        let jog = JogShuttle::new().with_id("jog").show(ui);
+        *ticks += jog.ticks;
+        *speed = jog.speed;
+        jog.response
+    }
+
+    fn button(pos: egui::Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn jog_reports_ticks_while_turned() {
+        // A quarter turn from the top of the wheel, clockwise to its right and then back. The
+        // drag only starts after the pointer has moved a little, so some of it is lost.
+        for (delta, expected) in [(20.0, 1..=8), (-20.0, -8..=-1)] {
+            let (mut ticks, mut speed) = (0, 0.0);
+            let mut harness = KnobHarness::new();
+            harness.drag_at(Vec2::new(0.0, -20.0), Vec2::new(delta, 20.0), |ui| {
+                show_wheel(ui, &mut ticks, &mut speed)
+            });
+            assert!(expected.contains(&ticks), "{ticks}");
+            assert_eq!(speed, 0.0);
+        }
+    }
+
+    #[test]
+    fn shuttle_springs_back_to_zero() {
+        let (mut ticks, mut speed) = (0, 0.0);
+        let mut harness = KnobHarness::new();
+        let center = harness
+            .idle(1, |ui| show_wheel(ui, &mut ticks, &mut speed))
+            .rect
+            .center();
+        // Grab the ring at the top and turn it clockwise.
+        let start = center - Vec2::new(0.0, 41.0);
+        let turned = center + Vec2::new(41.0, -20.0);
+        for event in [
+            Event::PointerMoved(start),
+            button(start, true),
+            Event::PointerMoved(start + Vec2::new(10.0, 0.0)),
+            Event::PointerMoved(turned),
+        ] {
+            harness.run(vec![event], |ui| show_wheel(ui, &mut ticks, &mut speed));
+        }
+        assert!(speed > 0.0 && speed <= 8.0, "{speed}");
+
+        harness.run(vec![button(turned, false)], |ui| {
+            show_wheel(ui, &mut ticks, &mut speed)
+        });
+        harness.idle(1, |ui| show_wheel(ui, &mut ticks, &mut speed));
+        assert_eq!(speed, 0.0);
+        assert_eq!(ticks, 0);
+    }
+}
@@ -0,0 +1,194 @@
+use std::fmt::Write;
+
+/// Appends a formatted value to a string.
+type ValueWriter<'a> = Box<dyn FnMut(f32, &mut String) + 'a>;
+
+/// How the knob's value is turned into text.
+pub(crate) enum LabelFormat<'a> {
+    /// Two decimal places, switching to scientific notation close to zero.
+    Default,
+    /// A fixed number of decimal places.
+    Decimals(usize),
+    /// Returns a new string for every value.
+    Format(Box<dyn FnMut(f32) -> String + 'a>),
+    /// Appends the value to an existing string, so no intermediate string is allocated.
+    Write(ValueWriter<'a>),
+}
+
+impl LabelFormat<'_> {
+    /// Appends the text for `value` to `buf`.
+    pub(crate) fn write(&mut self, value: f32, buf: &mut String) {
+        match self {
+            LabelFormat::Default => {
+                if value.abs() > 1e-2 || value == 0.0 {
+                    let _ = write!(buf, "{:.2}", value);
+                } else {
+                    // Display values close to zero in scientific power notation.
+                    // Otherwise they display as 0.0.
+                    let _ = write!(buf, "{:+.1e}", value);
+                }
+            }
+            LabelFormat::Decimals(decimals) => {
+                let _ = write!(buf, "{value:.decimals$}");
+            }
+            LabelFormat::Format(format) => buf.push_str(&format(value)),
+            LabelFormat::Write(write) => write(value, buf),
+        }
+    }
+
+    /// Appends the text for `value` to `buf`, shown as chosen by `display`.
+    pub(crate) fn write_display(
+        &mut self,
+        value: f32,
+        normalised: f32,
+        display: ValueDisplay,
+        buf: &mut String,
+    ) {
+        match display {
+            ValueDisplay::Value => self.write(value, buf),
+            ValueDisplay::Normalised => {
+                let _ = write!(buf, "{:.1}%", normalised * 100.0);
+            }
+            ValueDisplay::Both => {
+                self.write(value, buf);
+                let _ = write!(buf, " ({:.1}%)", normalised * 100.0);
+            }
+        }
+    }
+
+    /// The full label text for `value`: `"label: value"`, or only the value if `label` is empty.
+    pub(crate) fn label_text(&mut self, label: &str, value: f32) -> String {
+        let mut text = String::with_capacity(label.len() + 16);
+        if !label.is_empty() {
+            text.push_str(label);
+            text.push_str(": ");
+        }
+        self.write(value, &mut text);
+        text
+    }
+
+    /// Like [`LabelFormat::label_text`], but showing the value as chosen by `display`.
+    pub(crate) fn display_text(
+        &mut self,
+        label: &str,
+        value: f32,
+        normalised: f32,
+        display: ValueDisplay,
+    ) -> String {
+        let mut text = String::with_capacity(label.len() + 16);
+        if !label.is_empty() {
+            text.push_str(label);
+            text.push_str(": ");
+        }
+        self.write_display(value, normalised, display, &mut text);
+        text
+    }
+}
+
+/// The number of decimals needed to show multiples of `step`, up to six.
+pub(crate) fn step_decimals(step: f32) -> usize {
+    let step = step.abs() as f64;
+    (0..6)
+        .find(|&decimals| {
+            let scaled = step * 10f64.powi(decimals as i32);
+            // A step smaller than the last decimal shown would round away to nothing.
+            scaled.round() >= 1.0 && (scaled - scaled.round()).abs() < 1e-3
+        })
+        .unwrap_or(6)
+}
+
+/// The 16-point compass direction nearest to a heading in degrees, e.g. `"WSW"` for 245°.
+///
+/// ```
+/// assert_eq!(egui_fancy_knob::compass_point(245.0), "WSW");
+/// assert_eq!(egui_fancy_knob::compass_point(355.0), "N");
+/// ```
+pub fn compass_point(degrees: f32) -> &'static str {
+    const POINTS: [&str; 16] = [
+        "N", "NNE", "NE", "ENE", "E", "ESE", "SE", "SSE", "S", "SSW", "SW", "WSW", "W", "WNW",
+        "NW", "NNW",
+    ];
+    POINTS[(degrees.rem_euclid(360.0) / 22.5).round() as usize % 16]
+}
+
+/// What the label shows, see [`crate::Knob::with_value_display`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ValueDisplay {
+    /// The value, formatted by the label format
+    Value,
+    /// The knob's normalised position, as a percentage
+    Normalised,
+    /// The value followed by the normalised position, e.g. `"Cutoff: 632.46 (50.0%)"`
+    Both,
+}
+
+/// Parses typed or pasted text into a value.
+pub(crate) type ValueParser<'a> = Box<dyn Fn(&str) -> Option<f32> + 'a>;
+
+/// Parses the number at the start of `text`, ignoring a trailing unit such as `"%"` or `" dB"`.
+pub(crate) fn parse_leading_number(text: &str) -> Option<f32> {
+    let text = text.trim();
+    text.char_indices()
+        .map(|(index, c)| index + c.len_utf8())
+        .rev()
+        .find_map(|end| text[..end].trim_end().parse().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_decimals_fit_the_step() {
+        assert_eq!(step_decimals(1.0), 0);
+        assert_eq!(step_decimals(5.0), 0);
+        assert_eq!(step_decimals(0.5), 1);
+        assert_eq!(step_decimals(0.25), 2);
+        assert_eq!(step_decimals(0.01), 2);
+        assert_eq!(step_decimals(-0.1), 1);
+        assert_eq!(step_decimals(0.0004), 4);
+    }
+
+    #[test]
+    fn step_decimals_stop_at_six() {
+        assert_eq!(step_decimals(1.0 / 3.0), 6);
+        assert_eq!(step_decimals(1e-9), 6);
+    }
+
+    #[test]
+    fn parses_leading_numbers() {
+        assert_eq!(parse_leading_number("42"), Some(42.0));
+        assert_eq!(parse_leading_number(" -3.5 "), Some(-3.5));
+        assert_eq!(parse_leading_number("1e3"), Some(1000.0));
+    }
+
+    #[test]
+    fn ignores_trailing_units() {
+        assert_eq!(parse_leading_number("50%"), Some(50.0));
+        assert_eq!(parse_leading_number("-6 dB"), Some(-6.0));
+        assert_eq!(parse_leading_number("440.0 Hz"), Some(440.0));
+    }
+
+    #[test]
+    fn rejects_text_without_a_leading_number() {
+        assert_eq!(parse_leading_number(""), None);
+        assert_eq!(parse_leading_number("dB"), None);
+        assert_eq!(parse_leading_number("Gain: 3"), None);
+    }
+
+    #[test]
+    fn compass_points() {
+        assert_eq!(compass_point(0.0), "N");
+        assert_eq!(compass_point(22.5), "NNE");
+        assert_eq!(compass_point(90.0), "E");
+        assert_eq!(compass_point(245.0), "WSW");
+    }
+
+    #[test]
+    fn compass_points_wrap_around() {
+        assert_eq!(compass_point(355.0), "N");
+        assert_eq!(compass_point(360.0), "N");
+        assert_eq!(compass_point(-90.0), "W");
+        assert_eq!(compass_point(720.0 + 180.0), "S");
+    }
+}
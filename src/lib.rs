@@ -1,15 +1,122 @@
-use egui::{Align2, Color32, Rect, Response, Sense, Stroke, Ui, Vec2, Widget};
+use egui::emath::GuiRounding as _;
+use egui::text::{CCursor, CCursorRange, LayoutJob, LayoutSection};
+use egui::text_edit::TextEditState;
+use egui::{
+    Align2, Color32, DragValue, EventFilter, Frame, Id, LayerId, Modifiers, Painter, Rect,
+    Response, Sense, Stroke, TextEdit, Ui, Vec2, Widget, WidgetInfo,
+};
+use smallvec::SmallVec;
+use std::borrow::Cow;
+use std::cell::RefCell;
 use std::f32::consts::TAU;
 use std::ops::RangeInclusive;
+use std::time::Duration;
 
+mod accessibility;
+mod animation;
+mod bank;
+mod compat;
+mod curve;
+mod debug;
+mod eq;
+mod event;
+mod exposure;
+mod geometry;
+mod group;
+mod handle;
+mod interactions;
+mod jog;
+mod label;
+mod layout;
+mod link;
+mod macro_knob;
+mod mapping;
+mod midi;
+mod morph;
 mod normalise;
+mod param;
+mod pid;
+mod preset;
+mod ring;
+mod selection;
+mod state;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+mod tooltip;
+mod tuning;
 
+pub use animation::{Easing, KnobAnimations, reduced_motion, set_reduced_motion};
+use animation::{Sweep, ValueSweep, ValueSweepStep};
+pub use bank::{KnobBank, KnobBankLayout, KnobVisuals};
+use curve::Curve;
+pub use debug::{debug_overlay, set_debug_overlay};
+pub use eq::{EqBand, EqBandKnobs};
+pub use event::KnobEvent;
+pub use exposure::Exposure;
+pub use geometry::{KnobGeometry, KnobResponse};
+pub use group::{GroupMode, KnobGroup};
+pub use handle::KnobHandle;
+pub use interactions::{KnobInteraction, KnobInteractions};
+pub use jog::{JogShuttle, JogShuttleResponse};
+use label::{LabelFormat, ValueParser};
+pub use label::{ValueDisplay, compass_point};
+pub use layout::{InlineAlign, KnobLayout};
+pub use link::{KnobLink, LinkMode};
+pub use macro_knob::{MacroDestination, MacroKnob, MacroKnobInner};
+pub use mapping::KnobMapping;
+pub use midi::{KnobMidiMap, MidiCc};
+pub use morph::{MorphKnob, MorphKnobInner};
 use normalise::*;
+pub use param::{KnobParam, ParamKnob, ParamKnobInner};
+pub use pid::{PidGains, PidKnobs};
+pub use preset::{KnobPreset, PresetDifference};
+pub use ring::{ArcQuality, RingColor};
+pub use selection::KnobSelection;
+use state::{KnobBuffers, KnobState, Nudge, Staged};
+pub use tooltip::KnobInfo;
+use tooltip::Tooltip;
+pub use tuning::{TuningKnob, TuningKnobInner};
 
 const KNOB_FINE_DRAG_RATIO: f32 = 0.2;
-const INFINITY: f32 = f32::INFINITY;
+const KNOB_COARSE_DRAG_RATIO: f32 = 10.0;
+/// Pointer speed, in points per second, above which drags of stepped knobs skip steps.
+const VELOCITY_STEP_THRESHOLD: f32 = 300.0;
+/// Pointer speed above the threshold that adds another step per point dragged.
+const VELOCITY_STEP_SCALE: f32 = 300.0;
+/// Most steps per point dragged, however fast the pointer moves.
+const VELOCITY_STEP_MAX: f32 = 8.0;
+/// The range of motion of the knob. 1.0 means a full rotation.
+const KNOB_MOTION: f32 = 0.85;
 
-pub fn add_knob<F: Fn()>(ui: &mut Ui, knob: Knob<impl FnMut(f32)>, on_release: F) {
+/// Seconds a nudge button is held before it starts repeating.
+const NUDGE_DELAY: f64 = 0.4;
+/// Seconds between the first repeated nudges.
+const NUDGE_INTERVAL: f64 = 0.15;
+/// Shortest time between repeated nudges, once fully accelerated.
+const NUDGE_MIN_INTERVAL: f64 = 0.03;
+/// How much the time between nudges shrinks with every repeat.
+const NUDGE_ACCELERATION: f64 = 0.85;
+
+/// Analog stick deflection below which the stick is ignored.
+const STICK_DEAD_ZONE: f32 = 0.15;
+/// Normalised distance per second a fully deflected analog stick moves the knob.
+const STICK_SPEED: f32 = 0.5;
+
+/// Seconds after the last change before an unconfirmed change is reverted.
+const CONFIRM_TIMEOUT: f64 = 5.0;
+
+/// Seconds the playback indicator takes to catch up with a new playback value, smoothing over
+/// hosts that send values less often than the UI repaints.
+const PLAYBACK_SMOOTHING: f32 = 0.05;
+
+/// Furthest the indicator stretches past the end of its travel when a drag pushes beyond it,
+/// as a fraction of the travel.
+const RUBBER_BAND_MAX: f32 = 0.04;
+
+/// Pulses per second of the ring around a knob armed for MIDI learn.
+const LEARN_PULSE_RATE: f32 = 1.5;
+
+pub fn add_knob<F: Fn()>(ui: &mut Ui, knob: Knob<'_, impl FnMut(f32)>, on_release: F) {
     let response = ui.add(knob);
 
     if response.drag_stopped() || response.lost_focus() {
@@ -17,66 +124,411 @@ pub fn add_knob<F: Fn()>(ui: &mut Ui, knob: Knob<impl FnMut(f32)>, on_release: F
     }
 }
 
-#[derive(Clone)]
-struct KnobSpec {
-    logarithmic: bool,
-    /// For logarithmic knobs, the smallest positive value we are interested in before the knob
-    /// switches to `0.0`.
-    smallest_finite: f32,
-    /// For logarithmic knobs, the largest positive value we are interested in before the knob
-    /// switches to `INFINITY`.
-    largest_finite: f32,
+/// Where a range of motion of `motion` turns starts, so that it's symmetrical around the bottom.
+fn start_turn(motion: f32) -> f32 {
+    0.25 + (1.0 - motion) * 0.5
+}
+
+/// The normalised position whose indicator points at `angle`, in radians clockwise from
+/// pointing right, for a range of motion of `motion` turns starting at `start` turns. Angles in
+/// the gap outside the range of motion go to the nearest end.
+fn position_at_angle(angle: f32, start: f32, motion: f32) -> f32 {
+    let position = (angle / TAU - start).rem_euclid(1.0) / motion;
+    if position <= 1.0 {
+        position
+    } else if position < (1.0 + 1.0 / motion) / 2.0 {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Returns random numbers from `0.0` to `1.0`, see [`Knob::with_random_source`].
+type RandomSource<'a> = Box<dyn FnMut() -> f32 + 'a>;
+
+/// A random number from `0.0` to `1.0`, from the standard library's randomly seeded hasher.
+fn random_unit() -> f32 {
+    use std::hash::BuildHasher;
+    let bits = std::collections::hash_map::RandomState::new().hash_one(0u8);
+    (bits >> 40) as f32 / (1u64 << 24) as f32
+}
+
+/// Desaturates and fades a color, for drawing disabled knobs.
+fn dimmed(color: Color32) -> Color32 {
+    let [r, g, b, a] = color.to_array();
+    let grey = ((r as u16 * 3 + g as u16 * 6 + b as u16) / 10) as u8;
+    let grey = Color32::from_rgba_premultiplied(grey, grey, grey, a);
+    color.lerp_to_gamma(grey, 0.7).gamma_multiply(0.5)
+}
+
+/// A needle indicator pointing at `angle`: a tapered pointer `length` points long with a short
+/// tail, over a round hub.
+pub(crate) fn needle_shapes(
+    center: egui::Pos2,
+    angle: f32,
+    length: f32,
+    width: f32,
+    color: Color32,
+) -> [egui::Shape; 2] {
+    let direction = Vec2::angled(angle);
+    let side = direction.rot90() * width;
+    [
+        egui::Shape::convex_polygon(
+            vec![
+                center + direction * length,
+                center + side,
+                center - direction * (length * 0.15),
+                center - side,
+            ],
+            color,
+            Stroke::NONE,
+        ),
+        egui::Shape::circle_filled(center, width * 1.5, color),
+    ]
+}
+
+/// Paints a padlock `size` points tall, for read-only knobs.
+fn paint_lock(painter: &egui::Painter, center: egui::Pos2, size: f32, color: Color32) {
+    let body = Rect::from_center_size(
+        center + Vec2::new(0.0, size * 0.2),
+        Vec2::new(size * 0.7, size * 0.55),
+    );
+    painter.rect_filled(body, size * 0.08, color);
+    let shackle_radius = size * 0.22;
+    let shackle_center = egui::pos2(center.x, body.top());
+    let shackle = (0..=12)
+        .map(|i| {
+            let angle = std::f32::consts::PI * (1.0 + i as f32 / 12.0);
+            shackle_center + Vec2::angled(angle) * shackle_radius
+        })
+        .collect();
+    painter.add(egui::epaint::PathShape::line(
+        shackle,
+        Stroke::new(size * 0.1, color),
+    ));
+}
+
+/// Range calibration of a knob, see [`Knob::with_calibration`].
+struct Calibration<'a> {
+    modifiers: Modifiers,
+    on_calibrate: Box<dyn FnMut(RangeInclusive<f32>) + 'a>,
+}
+
+/// MIDI-learn state of a knob, see [`Knob::with_learn`].
+struct Learn<'a> {
+    armed: bool,
+    on_arm: Box<dyn FnMut(bool) + 'a>,
+}
+
+/// Where [`Knob::show`] placed the knob and its label.
+struct Placement {
+    layout: KnobLayout,
+    /// The allocated rect, including the frame.
+    outer_rect: Rect,
+    /// The knob and its label, inside the frame.
+    rect: Rect,
+    auto_id: Id,
+    right_to_left: bool,
+    rotated_label: bool,
+    label_truncated: bool,
+}
+
+impl Placement {
+    fn knob_rect(&self) -> Rect {
+        self.layout.knob_rect(self.rect)
+    }
+}
+
+/// What a frame of [`Knob::show`] has gathered so far, from the input through to painting.
+struct Pass {
+    response: Response,
+    state_id: Id,
+    state: KnobState,
+    buffers: KnobBuffers,
+    events: SmallVec<[KnobEvent; 4]>,
+    /// The value the setter last reported. A staged change is shown instead.
+    reported: f32,
+    read_only: bool,
+    externally_changed: bool,
+    /// Whether the value is being moved by a reset sweep this frame.
+    sweeping: bool,
+    /// Whether the value changed in a way that screen readers should announce, i.e. not by
+    /// dragging.
+    announce: bool,
+    /// Whether the value came from the other knob of a link, and isn't passed back to it.
+    linked: bool,
+    drag_delta: f32,
+    drag_total: f32,
+    detents_crossed: u32,
+    hit_min: bool,
+    hit_max: bool,
+    gesture: Option<Vec<(f64, f32)>>,
+    /// The nudge arrows and whether each is hovered, while shown.
+    arrows: Option<[(Rect, bool); 2]>,
+    /// The named values' disclosure arrow and whether it's hovered.
+    disclosure: Option<(Rect, bool)>,
+    /// Normalised position and value previewed under the pointer.
+    preview: Option<(f32, f32)>,
+}
+
+impl Pass {
+    /// Stores the knob's state and hands everything gathered to the caller.
+    fn finish(mut self, ctx: &egui::Context, geometry: KnobGeometry) -> KnobResponse {
+        self.state.last_geometry = Some(geometry);
+        self.state.store(ctx, self.state_id);
+        self.buffers.put_back(ctx, self.state_id);
+        KnobResponse {
+            response: self.response,
+            geometry: Some(geometry),
+            drag_delta: self.drag_delta,
+            drag_total: self.drag_total,
+            detents_crossed: self.detents_crossed,
+            hit_min: self.hit_min,
+            hit_max: self.hit_max,
+            events: self.events,
+            gesture: self.gesture,
+        }
+    }
+}
+
+/// Where and how [`Knob::show`] paints the knob this frame.
+struct Canvas {
+    painter: Painter,
+    geometry: KnobGeometry,
+    knob_rect: Rect,
+    /// The normalised position shown, which lags behind the value while animating.
+    displayed: f32,
+    knob_color: Color32,
+    segments_per_turn: f32,
+    flash_t: f32,
 }
 
 /// Position of the label relative to the knob
+///
+/// In right-to-left layouts, see [`egui::Layout::prefer_right_to_left`], `Left` and `Right`
+/// swap sides and side labels are aligned to the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum LabelPosition {
     Top,
     Bottom,
     Left,
     Right,
+    /// Before the knob in reading order: left of it, or right of it in right-to-left layouts
+    Leading,
+    /// After the knob in reading order: right of it, or left of it in right-to-left layouts
+    Trailing,
+    /// Beside the knob, [`LabelPosition::Trailing`], when there's room for both across the
+    /// width the parent [`Ui`] offers, and below it otherwise. The same knob then suits both
+    /// wide toolbars and narrow side panels.
+    Auto,
+}
+
+impl LabelPosition {
+    /// The side the label goes on, given whether the layout is right-to-left.
+    pub(crate) fn resolve(self, right_to_left: bool) -> Self {
+        match (self, right_to_left) {
+            (LabelPosition::Left | LabelPosition::Leading, false)
+            | (LabelPosition::Right | LabelPosition::Trailing, true) => LabelPosition::Left,
+            (LabelPosition::Right | LabelPosition::Trailing, false)
+            | (LabelPosition::Left | LabelPosition::Leading, true) => LabelPosition::Right,
+            (position, _) => position,
+        }
+    }
+}
+
+/// Extra rendering behind the label text, to keep it legible over busy backgrounds
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LabelEffect {
+    /// Plain text
+    None,
+    /// A 1 point outline around the text
+    Outline(Color32),
+    /// A drop shadow, offset from the text
+    Shadow { color: Color32, offset: Vec2 },
+}
+
+/// Automation mode of the parameter behind a knob, as in a DAW
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AutomationState {
+    /// Automation is played back
+    Read,
+    /// Automation is written while the knob is held
+    Touch,
+    /// Automation is written continuously
+    Write,
+    /// Automation is written from the first touch until playback stops
+    Latch,
+}
+
+impl AutomationState {
+    /// Color of the badge drawn for this state
+    pub fn color(self) -> Color32 {
+        match self {
+            AutomationState::Read => Color32::from_rgb(80, 200, 120),
+            AutomationState::Touch => Color32::from_rgb(240, 190, 60),
+            AutomationState::Write => Color32::from_rgb(230, 70, 70),
+            AutomationState::Latch => Color32::from_rgb(170, 110, 240),
+        }
+    }
+
+    /// Single letter shown in the badge for this state
+    pub fn letter(self) -> &'static str {
+        match self {
+            AutomationState::Read => "R",
+            AutomationState::Touch => "T",
+            AutomationState::Write => "W",
+            AutomationState::Latch => "L",
+        }
+    }
+}
+
+/// What is copied to the clipboard from a knob, see [`Knob::with_clipboard`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ClipboardValue {
+    /// The value as displayed by the label format, e.g. `"42.0%"`
+    Formatted,
+    /// The plain number, e.g. `"0.42"`
+    Raw,
+}
+
+/// What a knob does with a value outside its range, see [`Knob::with_out_of_range`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutOfRange {
+    /// Shows the nearest value in the range
+    #[default]
+    Clamp,
+    /// Logs a warning and shows the nearest value in the range
+    Warn,
+    /// Shows the value as it is, with the indicator at the end of the range in the warning
+    /// color. The value is only changed when the user moves the knob
+    Preserve,
+}
+
+/// A knob's value being dragged onto another knob, see [`Knob::with_drag_and_drop`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KnobPayload {
+    /// Id of the knob the value was dragged from
+    pub source: Id,
+    /// The dragged value
+    pub value: f32,
+    /// The dragged value, normalised to the source knob's range
+    pub normalised: f32,
 }
 
 /// Visual style of the knob indicator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum KnobStyle {
     /// A line extending from the center to the edge
     Wiper,
     /// A dot on the edge of the knob
     Dot,
+    /// A tapered needle over a hub in the center, as on a gauge
+    Needle,
 }
 
 /// A circular knob widget for egui that can be dragged to change a value
 ///
 /// # Example
 /// ```
+/// # use egui_fancy_knob::{Knob, KnobStyle, LabelPosition};
 /// let mut value = 0.5;
-/// Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+/// Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
 ///     .with_size(50.0)
 ///     .with_label("Volume", LabelPosition::Bottom)
 ///     .with_step(0.1);
 /// ```
-pub struct Knob<F: FnMut(f32)> {
+pub struct Knob<'a, F: FnMut(f32)> {
     value: f32,
     set_value: F,
     range: RangeInclusive<f32>,
     spec: KnobSpec,
     size: f32,
+    min_size: Option<f32>,
+    max_size: Option<f32>,
     font_size: f32,
     stroke_width: f32,
     knob_color: Color32,
     knob_dragging_color: Color32,
     line_color: Color32,
     text_color: Color32,
-    label: Option<String>,
+    ring_color: RingColor,
+    scaled_arc: Option<(f32, f32)>,
+    fine_ring: Option<u32>,
+    step_badge: bool,
+    named_values: &'a [(&'a str, f32)],
+    bipolar_colors: Option<(Color32, Color32)>,
+    motion: f32,
+    rotation: f32,
+    wrap: bool,
+    mirrored: bool,
+    dial_labels: &'a [&'a str],
+    center_value: bool,
+    flash_color: Option<Color32>,
+    frame: Option<Frame>,
+    label: Option<Cow<'a, str>>,
     label_position: LabelPosition,
+    label_effect: LabelEffect,
+    label_swatch: Option<Color32>,
+    rotated_label: bool,
+    two_line_label: bool,
+    value_font_size: Option<f32>,
+    value_color: Option<Color32>,
+    inline_align: InlineAlign,
     style: KnobStyle,
     label_offset: f32,
-    label_format: Box<dyn FnMut(f32) -> String>,
+    label_format: LabelFormat<'a>,
+    value_parser: Option<ValueParser<'a>>,
+    out_of_range: OutOfRange,
+    tooltip: Option<Tooltip<'a>>,
+    curve: Option<Curve<'a>>,
+    value_display: ValueDisplay,
+    clipboard: Option<ClipboardValue>,
+    random: Option<RandomSource<'a>>,
+    drag_and_drop: Option<Modifiers>,
+    absolute_drag: Option<Modifiers>,
+    coarse_drag: Option<Modifiers>,
+    group: Option<&'a KnobGroup>,
+    link: Option<&'a KnobLink>,
+    selection: Option<&'a KnobSelection>,
+    hover_preview: Option<Modifiers>,
+    hold_to_nudge: bool,
+    latch: bool,
+    nudge_arrows: bool,
+    flip_to_neutral: bool,
+    confirm: bool,
+    pen_pressure: bool,
+    velocity_stepping: bool,
+    record_gestures: bool,
+    magnifier_size: Option<f32>,
+    persist: bool,
+    layer_id: Option<LayerId>,
     step: Option<f32>,
+    fine_step: Option<(f32, Modifiers)>,
     neutral: Option<f32>,
     enabled: bool,
+    read_only: bool,
+    dim_when_disabled: bool,
+    animations: KnobAnimations,
+    emit_reset_sweep: bool,
+    learn: Option<Learn<'a>>,
+    calibration: Option<Calibration<'a>>,
+    automation_state: Option<AutomationState>,
+    playback: Option<(f32, Color32)>,
+    actual: Option<(f32, Color32)>,
+    deviation: Option<(f32, Color32)>,
+    min_detail_size: f32,
+    fallback_width: Option<f32>,
+    layout: Option<KnobLayout>,
+    debug: bool,
+    arc_quality: ArcQuality,
+    margin: f32,
+    label_padding: f32,
+    hit_padding: f32,
+    lock_scroll: bool,
+    id: Option<Id>,
 }
 
-impl<F: FnMut(f32)> Knob<F> {
+impl<'a, F: FnMut(f32)> Knob<'a, F> {
     /// Creates a new knob widget
     ///
     /// # Arguments
@@ -87,46 +539,221 @@ impl<F: FnMut(f32)> Knob<F> {
     /// * `spec` - Parameters for a logarithmic knob
     pub fn new(value: f32, set_value: F, range: RangeInclusive<f32>, style: KnobStyle) -> Self {
         Self {
-            value: value.clamp(*range.start(), *range.end()),
+            value,
             set_value,
             range,
-            spec: KnobSpec {
-                logarithmic: false,
-                smallest_finite: 1e-6,
-                largest_finite: 1e6,
-            },
+            spec: KnobSpec::default(),
             size: 40.0,
+            min_size: None,
+            max_size: None,
             font_size: 12.0,
             stroke_width: 2.0,
             knob_color: Color32::GRAY,
             knob_dragging_color: Color32::WHITE,
             line_color: Color32::GRAY,
             text_color: Color32::WHITE,
+            ring_color: RingColor::Solid,
+            scaled_arc: None,
+            fine_ring: None,
+            step_badge: false,
+            named_values: &[],
+            bipolar_colors: None,
+            motion: KNOB_MOTION,
+            rotation: 0.0,
+            wrap: false,
+            mirrored: false,
+            dial_labels: &[],
+            center_value: false,
+            flash_color: None,
+            frame: None,
             label: None,
             label_position: LabelPosition::Bottom,
+            label_effect: LabelEffect::None,
+            label_swatch: None,
+            rotated_label: false,
+            two_line_label: false,
+            value_font_size: None,
+            value_color: None,
+            inline_align: InlineAlign::Whole,
             style,
             label_offset: 1.0,
-            label_format: Box::new(|v| {
-                if v.abs() > 1e-2 || v == 0.0 {
-                    format!("{:.2}", v)
-                } else {
-                    // Display values close to zero in scientific power notation.
-                    // Otherwise they display as 0.0.
-                    format!("{:+.1e}", v)
-                }
-            }),
+            label_format: LabelFormat::Default,
+            value_parser: None,
+            out_of_range: OutOfRange::Clamp,
+            tooltip: None,
+            curve: None,
+            value_display: ValueDisplay::Value,
+            clipboard: None,
+            random: None,
+            drag_and_drop: None,
+            absolute_drag: None,
+            coarse_drag: None,
+            group: None,
+            link: None,
+            selection: None,
+            hover_preview: None,
+            hold_to_nudge: false,
+            latch: false,
+            nudge_arrows: false,
+            flip_to_neutral: false,
+            confirm: false,
+            pen_pressure: false,
+            velocity_stepping: false,
+            record_gestures: false,
+            magnifier_size: None,
+            persist: false,
+            layer_id: None,
             step: None,
+            fine_step: None,
             neutral: None,
             enabled: true,
+            read_only: false,
+            dim_when_disabled: true,
+            animations: KnobAnimations::default(),
+            emit_reset_sweep: false,
+            learn: None,
+            calibration: None,
+            automation_state: None,
+            playback: None,
+            actual: None,
+            deviation: None,
+            min_detail_size: 16.0,
+            fallback_width: None,
+            layout: None,
+            debug: cfg!(feature = "extra_debug"),
+            arc_quality: ArcQuality::Auto,
+            margin: 4.0,
+            label_padding: layout::LABEL_PADDING,
+            hit_padding: 0.0,
+            lock_scroll: false,
+            id: None,
+        }
+    }
+
+    /// A gauge for monitoring dashboards: a needle over a 270° sweep with safe, warning and
+    /// danger zones, and the value in large text in the center.
+    ///
+    /// The zones start at 70% and 90% of the range, see [`RingColor::Zones`] to move them.
+    /// Combine with [`Knob::read_only`] to show a live value from elsewhere.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::Knob;
+    /// # let ctx = egui::Context::default();
+    /// # let _ = ctx.run(Default::default(), |ctx| {
+    /// # egui::CentralPanel::default().show(ctx, |ui| {
+    /// let temperature = 72.5;
+    /// ui.add(Knob::gauge(temperature, |_| {}, 0.0..=120.0).read_only(true));
+    /// # });
+    /// # });
+    /// ```
+    pub fn gauge(value: f32, set_value: F, range: RangeInclusive<f32>) -> Self {
+        let (start, end) = (*range.start(), *range.end());
+        Self::new(value, set_value, range, KnobStyle::Needle)
+            .with_size(80.0)
+            .with_sweep(270.0)
+            .with_ring_color(RingColor::Zones {
+                warn: egui::lerp(start..=end, 0.7),
+                danger: egui::lerp(start..=end, 0.9),
+                safe: Color32::from_rgb(80, 180, 90),
+                caution: Color32::from_rgb(230, 180, 50),
+                critical: Color32::from_rgb(220, 70, 60),
+            })
+            .with_stroke_width(4.0)
+            .center_value(true)
+    }
+
+    /// A heading knob for robotics, drone and mapping UIs: a full turn from 0° to 360° with north
+    /// at the top, wrapping around, labeled N, E, S and W around the dial, and showing values
+    /// like `"245° (WSW)"`.
+    pub fn compass(value: f32, set_value: F) -> Self {
+        Self::new(value, set_value, 0.0..=360.0, KnobStyle::Needle)
+            .with_sweep(360.0)
+            .with_rotation(180.0)
+            .wrap(true)
+            .with_dial_labels(&["N", "E", "S", "W"])
+            .with_label_format(|degrees| {
+                let degrees = degrees.round().rem_euclid(360.0);
+                format!("{degrees:.0}° ({})", compass_point(degrees))
+            })
+    }
+
+    /// A camera exposure knob in stops, snapping to the standard 1/3 EV sequence and showing
+    /// values like `"f/5.6"`, `"1/250 s"` or `"+0.7 EV"`, see [`Exposure`].
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Exposure, Knob};
+    /// let mut aperture = 5.0; // f/5.6
+    /// Knob::exposure(aperture, |stops| aperture = stops, Exposure::Aperture);
+    /// ```
+    pub fn exposure(value: f32, set_value: F, exposure: Exposure) -> Self {
+        let knob = Self::new(value, set_value, exposure.range(), KnobStyle::Dot)
+            .with_step(1.0 / 3.0)
+            .with_label_format(move |stops| exposure.format(stops));
+        match exposure {
+            Exposure::Compensation => knob.with_neutral(0.0),
+            Exposure::Aperture | Exposure::Shutter => knob,
         }
     }
 
+    /// An 8-bit intensity knob for stage lighting: whole steps from 0 to 255.
+    ///
+    /// Show percentages instead with [`Knob::with_value_display`], and the channel's color with
+    /// [`Knob::with_label_swatch`].
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, LabelPosition, ValueDisplay};
+    /// let mut red = 255.0;
+    /// Knob::dmx(red, |v| red = v)
+    ///     .with_label("Red", LabelPosition::Bottom)
+    ///     .with_label_swatch(egui::Color32::RED)
+    ///     .with_value_display(ValueDisplay::Normalised);
+    /// ```
+    pub fn dmx(value: f32, set_value: F) -> Self {
+        Self::new(value, set_value, 0.0..=255.0, KnobStyle::Wiper)
+            .with_step(1.0)
+            .with_label_format(|value| format!("{value:.0}"))
+    }
+
+    /// A stereo width knob from mono to 200%, with a mirrored indicator that spreads apart as
+    /// the width grows. Double clicking resets to 100%.
+    pub fn stereo_width(value: f32, set_value: F) -> Self {
+        Self::new(value, set_value, 0.0..=2.0, KnobStyle::Wiper)
+            .mirrored(true)
+            .with_neutral(1.0)
+            .with_label_format(|width| {
+                if width < 0.005 {
+                    "Mono".to_owned()
+                } else {
+                    format!("{:.0} %", width * 100.0)
+                }
+            })
+    }
+
     /// Sets the size of the knob
     pub fn with_size(mut self, size: f32) -> Self {
         self.size = size;
         self
     }
 
+    /// Lets the knob shrink when its parent layout is too narrow for it, after narrowing the
+    /// label, down to `size`. Without a minimum size the knob keeps its size.
+    pub fn with_min_size(mut self, size: f32) -> Self {
+        let size = size.max(0.0);
+        self.min_size = Some(size);
+        self.max_size = self.max_size.map(|max_size| max_size.max(size));
+        self
+    }
+
+    /// Sets the largest size of the knob, also capping sizes from [`Knob::with_size`] and
+    /// [`Knob::with_layout`]. It's never below the minimum size.
+    pub fn with_max_size(mut self, size: f32) -> Self {
+        self.max_size = Some(size.max(self.min_size.unwrap_or(0.0)));
+        self
+    }
+
     /// Sets the font size for the label
     pub fn with_font_size(mut self, size: f32) -> Self {
         self.font_size = size;
@@ -159,281 +786,3009 @@ impl<F: FnMut(f32)> Knob<F> {
         self
     }
 
+    /// Sets the color of the label text, leaving the other colors as they are.
+    pub fn with_text_color(mut self, text_color: Color32) -> Self {
+        self.text_color = text_color;
+        self
+    }
+
+    /// Sets the style, stroke width, colors, margin and label padding from `visuals`, leaving
+    /// the text color as is.
+    ///
+    /// Takes the visuals by reference, so one [`KnobVisuals`] can be shared by many knobs
+    /// without cloning it for each one every frame.
+    pub fn with_visuals(mut self, visuals: &KnobVisuals) -> Self {
+        self.style = visuals.style;
+        self.stroke_width = visuals.stroke_width;
+        self.knob_color = visuals.knob_color;
+        self.knob_dragging_color = visuals.knob_dragging_color;
+        self.line_color = visuals.line_color;
+        self.margin = visuals.margin.max(0.0);
+        self.label_padding = visuals.label_padding.max(0.0);
+        self
+    }
+
+    /// Sets how the knob's outer ring is colored
+    ///
+    /// The default is [`RingColor::Solid`], which uses the colors from [`Knob::with_colors`].
+    /// Gradient and threshold rings are not recolored while dragging.
+    pub fn with_ring_color(mut self, ring_color: RingColor) -> Self {
+        self.ring_color = ring_color;
+        self
+    }
+
+    /// Draws an arc over the ring from the start of the range to the value, which widens from
+    /// `min_width` at the minimum to `max_width` at the maximum, so intensity parameters like
+    /// drive or reverb amount read at a glance. The arc uses the line color.
+    pub fn with_scaled_arc(mut self, min_width: f32, max_width: f32) -> Self {
+        self.scaled_arc = Some((min_width, max_width));
+        self
+    }
+
+    /// Draws two readout rings: an outer one showing the position across the whole range, and an
+    /// inner one showing the position within the current one of `coarse_steps` equal steps. This
+    /// makes small changes to very fine parameters visible, e.g. tuning in cents.
+    pub fn with_fine_ring(mut self, coarse_steps: u32) -> Self {
+        self.fine_ring = Some(coarse_steps.max(1));
+        self
+    }
+
+    /// Draws an arc over the ring from the neutral value to the value, in `negative` below
+    /// neutral and `positive` above it, e.g. for pan or detune. Without a neutral value from
+    /// [`Knob::with_neutral`], the arc starts at the middle of the range.
+    pub fn with_bipolar_colors(mut self, negative: Color32, positive: Color32) -> Self {
+        self.bipolar_colors = Some((negative, positive));
+        self
+    }
+
+    /// Sets the knob's range of motion in degrees, centered on the top. The default is 306°.
+    pub fn with_sweep(mut self, degrees: f32) -> Self {
+        self.motion = (degrees / 360.0).clamp(0.01, 1.0);
+        self
+    }
+
+    /// Rotates the range of motion clockwise by `degrees`, e.g. `180.0` to start a full-turn
+    /// knob at the top instead of the bottom.
+    pub fn with_rotation(mut self, degrees: f32) -> Self {
+        self.rotation = degrees / 360.0;
+        self
+    }
+
+    /// Whether dragging, nudging and stepping past one end of the range continues from the
+    /// other, e.g. for angles. The default is OFF.
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Whether the indicator is drawn twice, mirrored around the middle of the range: both
+    /// point to the middle at the start of the range and spread apart towards either end as
+    /// the value grows, as on stereo width knobs. The default is OFF.
+    pub fn mirrored(mut self, mirrored: bool) -> Self {
+        self.mirrored = mirrored;
+        self
+    }
+
+    /// Labels spread evenly around the dial, the first at the start of the range. With a full
+    /// turn the last label is one gap before the end, which is the start again.
+    pub fn with_dial_labels(mut self, labels: &'a [&'a str]) -> Self {
+        self.dial_labels = labels;
+        self
+    }
+
+    /// Whether the value is shown in large text in the center of the knob, as on a gauge. The
+    /// default is OFF.
+    pub fn center_value(mut self, center_value: bool) -> Self {
+        self.center_value = center_value;
+        self
+    }
+
+    /// Briefly highlights the ring whenever the value is changed from outside the knob
+    ///
+    /// This makes parameters moved by presets, MIDI or automation noticeable. The highlight
+    /// fades out over [`KnobAnimations::flash`].
+    pub fn with_change_flash(mut self, color: Color32) -> Self {
+        self.flash_color = Some(color);
+        self
+    }
+
+    /// Draws a background frame behind the knob and its label
+    ///
+    /// The frame's margins, fill, stroke, corner radius and shadow are applied the same way as
+    /// [`Frame::show`], so knobs can be grouped visually without wrapping each one.
+    pub fn with_frame(mut self, frame: Frame) -> Self {
+        self.frame = Some(frame);
+        self
+    }
+
+    /// Sets how finely gradient and threshold rings are tessellated
+    ///
+    /// The default is [`ArcQuality::Auto`]. Low-power targets can lower it to trade smoothness
+    /// for speed.
+    pub fn with_arc_quality(mut self, quality: ArcQuality) -> Self {
+        self.arc_quality = quality;
+        self
+    }
+
+    /// Paints the knob into `layer_id` instead of the [`Ui`]'s own layer.
+    ///
+    /// Use a foreground or tooltip layer to draw the knob above other panels, e.g. for a
+    /// magnified detail knob. Interaction still happens in the [`Ui`]'s layer, and the painting
+    /// is clipped to the [`Ui`]'s clip rect.
+    pub fn with_layer(mut self, layer_id: LayerId) -> Self {
+        self.layer_id = Some(layer_id);
+        self
+    }
+
+    /// Sets the space added above and below the knob. The default is 4 points; use 0 for
+    /// pixel-precise custom layouts.
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin.max(0.0);
+        self
+    }
+
+    /// Sets the space around the label text. The default is 2 points.
+    pub fn with_label_padding(mut self, padding: f32) -> Self {
+        self.label_padding = padding.max(0.0);
+        self
+    }
+
+    /// Extends the interactive area by `padding` points on every side, beyond what's drawn.
+    ///
+    /// This makes small knobs easier to hit on touch screens, e.g. padding a 24 point knob by
+    /// 10 points gives a 44 point touch target, without changing the layout. The padding may
+    /// overlap neighbouring widgets. The default is 0.
+    pub fn with_hit_padding(mut self, padding: f32) -> Self {
+        self.hit_padding = padding.max(0.0);
+        self
+    }
+
+    /// Whether scrolling is kept from containers such as a [`egui::ScrollArea`] or a plot for as
+    /// long as the pointer is over the knob, or its value is being typed in, instead of only while
+    /// it's pressed or dragged. The default is OFF.
+    pub fn lock_scroll(mut self, lock_scroll: bool) -> Self {
+        self.lock_scroll = lock_scroll;
+        self
+    }
+
     /// Adds a label to the knob
     ///
     /// # Arguments
     /// * `label` - Text to display
     /// * `position` - Position of the label relative to the knob
-    pub fn with_label(mut self, label: impl Into<String>, position: LabelPosition) -> Self {
+    pub fn with_label(mut self, label: impl Into<Cow<'a, str>>, position: LabelPosition) -> Self {
         self.label = Some(label.into());
         self.label_position = position;
         self
     }
 
+    /// Whether a label on the left or right is turned 90° to run along the knob's side, reading
+    /// upwards on the left and downwards on the right. This saves width in tightly packed channel
+    /// strips. The default is OFF.
+    pub fn rotated_label(mut self, rotated_label: bool) -> Self {
+        self.rotated_label = rotated_label;
+        self
+    }
+
+    /// Sets how the knob lines up with the widgets after it in horizontal layouts, instead of
+    /// aligning its label and margins along with it. The default is [`InlineAlign::Whole`].
+    pub fn with_inline_align(mut self, inline_align: InlineAlign) -> Self {
+        self.inline_align = inline_align;
+        self
+    }
+
+    /// Whether the label shows the name on one line and the value on a line below it, instead
+    /// of `"Name: value"`, for narrow knob columns. The default is OFF.
+    pub fn two_line_label(mut self, two_line_label: bool) -> Self {
+        self.two_line_label = two_line_label;
+        self
+    }
+
+    /// Sets the font size and color of the value line of a [`Knob::two_line_label`]. The
+    /// default is the same as the name.
+    pub fn with_value_style(mut self, font_size: f32, color: Color32) -> Self {
+        self.value_font_size = Some(font_size);
+        self.value_color = Some(color);
+        self
+    }
+
     /// Sets the spacing between the knob and its label
     pub fn with_label_offset(mut self, offset: f32) -> Self {
         self.label_offset = offset;
         self
     }
 
+    /// Draws a small square of `color` before the label text, e.g. a lighting channel's color
+    pub fn with_label_swatch(mut self, color: Color32) -> Self {
+        self.label_swatch = Some(color);
+        self
+    }
+
+    /// Draws an outline or drop shadow behind the label text
+    pub fn with_label_effect(mut self, effect: LabelEffect) -> Self {
+        self.label_effect = effect;
+        self
+    }
+
     /// Sets a custom format function for displaying the value
     ///
     /// # Example
     /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
     /// # let mut value = 0.5;
-    /// Knob::new(&mut value, 0.0, 1.0, KnobStyle::Wiper)
+    /// Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
     ///     .with_label_format(|v| format!("{:.1}%", v * 100.0));
     /// ```
-    pub fn with_label_format(mut self, format: impl FnMut(f32) -> String + 'static) -> Self {
-        self.label_format = Box::new(format);
+    pub fn with_label_format(mut self, format: impl FnMut(f32) -> String + 'a) -> Self {
+        self.label_format = LabelFormat::Format(Box::new(format));
         self
     }
 
-    /// Sets the step size for value changes.
+    /// Sets a custom format function that appends the value to the label text
     ///
-    /// When set, the value will snap to discrete steps as the knob is dragged.
-    pub fn with_step(mut self, step: f32) -> Self {
-        self.step = Some(step);
+    /// Unlike [`Knob::with_label_format`] this doesn't allocate a new string for every value,
+    /// which adds up when drawing hundreds of knobs per frame.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// # use std::fmt::Write;
+    /// # let mut value = 0.5;
+    /// Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
+    ///     .with_label_writer(|v, buf| {
+    ///         let _ = write!(buf, "{:.1}%", v * 100.0);
+    ///     });
+    /// ```
+    pub fn with_label_writer(mut self, write: impl FnMut(f32, &mut String) + 'a) -> Self {
+        self.label_format = LabelFormat::Write(Box::new(write));
         self
     }
 
-    /// Sets the neutral value.
+    /// Shows the knob's normalised position as a percentage, instead of or alongside the value.
     ///
-    /// When the knob is double clicked, it will reset to the neutral value.
-    pub fn with_neutral(mut self, neutral: f32) -> Self {
-        self.neutral = Some(neutral);
+    /// Handy while designing tapers. The default is [`ValueDisplay::Value`].
+    pub fn with_value_display(mut self, display: ValueDisplay) -> Self {
+        self.value_display = display;
         self
     }
 
-    pub fn enabled(mut self, enabled: bool) -> Self {
-        self.enabled = enabled;
+    /// Sets how text is turned back into a value, e.g. when pasting.
+    ///
+    /// Return `None` to reject the text. The result is clamped to the knob's range. The default
+    /// parses the number at the start of the text, ignoring any unit after it.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// # let mut value = 0.5;
+    /// Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
+    ///     .with_label_format(|v| format!("{:.1}%", v * 100.0))
+    ///     .with_value_parser(|text| {
+    ///         let percent: f32 = text.trim().trim_end_matches('%').parse().ok()?;
+    ///         Some(percent / 100.0)
+    ///     });
+    /// ```
+    pub fn with_value_parser(mut self, parse: impl Fn(&str) -> Option<f32> + 'a) -> Self {
+        self.value_parser = Some(Box::new(parse));
         self
     }
 
-    /// Make this a logarithmic knob.
-    /// The default is OFF.
-    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
-        self.spec.logarithmic = logarithmic;
+    /// Sets what happens when the value given to [`Knob::new`] is outside the range, e.g. one
+    /// loaded from a project saved with a different range. The default is
+    /// [`OutOfRange::Clamp`].
+    ///
+    /// Clamping only changes what the knob shows, the setter isn't called until the user moves
+    /// the knob.
+    pub fn with_out_of_range(mut self, out_of_range: OutOfRange) -> Self {
+        self.out_of_range = out_of_range;
         self
     }
 
-    /// For logarithmic knobs that include zero.
-    /// What is the smallest possible value that can be selected
-    /// before the value goes to zero.
-    /// Value is absolute so works for ranges `0..=x` and `x..=0`.
-    pub fn smallest_finite(mut self, smallest_finite: f32) -> Self {
-        self.spec.smallest_finite = smallest_finite.abs();
+    /// Shows a tooltip built by `add_contents` while the knob is hovered, e.g. with a
+    /// description of the parameter or keyboard hints.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// # let ctx = egui::Context::default();
+    /// # let _ = ctx.run(Default::default(), |ctx| {
+    /// # egui::CentralPanel::default().show(ctx, |ui| {
+    /// # let mut value = 0.5;
+    /// ui.add(
+    ///     Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
+    ///         .with_neutral(0.5)
+    ///         .with_tooltip(|ui, info| {
+    ///             ui.strong("Resonance");
+    ///             ui.label(format!("{} (default {:?})", info.text, info.neutral));
+    ///             if info.modified {
+    ///                 ui.weak("Double click to reset");
+    ///             }
+    ///         }),
+    /// );
+    /// # });
+    /// # });
+    /// ```
+    pub fn with_tooltip(mut self, add_contents: impl FnOnce(&mut Ui, &KnobInfo) + 'a) -> Self {
+        self.tooltip = Some(Box::new(add_contents));
         self
     }
 
-    /// For logarithmic knobs that go to infinity.
-    /// What is the largest possible value that can be selected
-    /// before the value goes to infinity.
-    /// Value is absolute so works for ranges `NEG_INFINITY..=x` and `x..=NEG_INFINITY`.
-    pub fn largest_finite(mut self, largest_finite: f32) -> Self {
-        self.spec.largest_finite = largest_finite.abs();
+    /// Copies the value with Ctrl+C (Cmd+C on Mac) and pastes it with Ctrl+V, while the knob is
+    /// focused or hovered.
+    ///
+    /// Pasted text goes through [`Knob::with_value_parser`]. The default is OFF.
+    pub fn with_clipboard(mut self, copy: ClipboardValue) -> Self {
+        self.clipboard = Some(copy);
         self
     }
-}
-
-impl<F: FnMut(f32)> Widget for Knob<F> {
-    fn ui(mut self, ui: &mut Ui) -> Response {
-        let knob_size = Vec2::splat(self.size);
-        let min = *self.range.start();
-        let max = *self.range.end();
-        let label_size = if let Some(label) = &self.label {
-            let font_id = egui::FontId::proportional(self.font_size);
-            let max_text = format!("{}: {}", label, (self.label_format)(max));
-            ui.painter()
-                .layout(max_text, font_id, Color32::WHITE, INFINITY)
-                .size()
-        } else {
-            Vec2::ZERO
-        };
 
-        let label_padding = 2.0;
-        let vertical_margin = 4.0;
+    /// While `modifiers` are held during a drag, the indicator follows the pointer's angle
+    /// around the knob, for quick coarse jumps. Releasing them goes back to relative dragging
+    /// from the new position.
+    ///
+    /// The modifiers take precedence over fine dragging, but shouldn't match the ones given to
+    /// [`Knob::with_drag_and_drop`].
+    pub fn with_absolute_drag(mut self, modifiers: Modifiers) -> Self {
+        self.absolute_drag = Some(modifiers);
+        self
+    }
 
-        ui.add_space(vertical_margin);
+    /// While `modifiers` are held during a drag, the value moves ten times faster, for quick
+    /// traversal of large ranges. The hover tooltip mentions both the fine and the coarse
+    /// modifiers, unless [`Knob::with_tooltip`] replaces it.
+    ///
+    /// The modifiers take precedence over fine dragging.
+    pub fn with_coarse_drag(mut self, modifiers: Modifiers) -> Self {
+        self.coarse_drag = Some(modifiers);
+        self
+    }
 
-        let adjusted_size = match self.label_position {
-            LabelPosition::Top | LabelPosition::Bottom => Vec2::new(
-                knob_size.x.max(label_size.x + label_padding * 2.0),
-                knob_size.y + label_size.y + label_padding * 2.0 + self.label_offset,
-            ),
-            LabelPosition::Left | LabelPosition::Right => Vec2::new(
-                knob_size.x + label_size.x + label_padding * 2.0 + self.label_offset,
-                knob_size.y.max(label_size.y + label_padding * 2.0),
-            ),
-        };
+    /// Whether the knob's context menu has a "Randomize" item, and pressing R on the focused
+    /// knob sets it to a random value. Values are spread evenly over the knob's travel and
+    /// snapped to its step. The default is OFF.
+    pub fn randomize(mut self, randomize: bool) -> Self {
+        self.random = randomize.then(|| Box::new(random_unit) as RandomSource<'a>);
+        self
+    }
 
-        let (rect, mut response) = ui.allocate_exact_size(adjusted_size, Sense::click_and_drag());
+    /// Like [`Knob::randomize`], but taking random numbers from `0.0` to `1.0` from `random`,
+    /// e.g. a seeded generator for deterministic tests.
+    pub fn with_random_source(mut self, random: impl FnMut() -> f32 + 'a) -> Self {
+        self.random = Some(Box::new(random));
+        self
+    }
 
-        if self.enabled {
-            // Double click to return to neutral state.
-            if response.double_clicked() {
-                if let Some(neutral) = self.neutral {
-                    if neutral != self.value {
-                        (self.set_value)(neutral);
-                        response.mark_changed();
-                    }
-                }
-            } else if response.dragged() {
-                let mut delta = response.drag_delta().y;
+    /// Locks the knob to the other knob in `link`, which follows every change of this one. The
+    /// knob needs an id from [`Knob::with_id`] that's one of the link's.
+    pub fn with_link(mut self, link: &'a KnobLink) -> Self {
+        self.link = Some(link);
+        self
+    }
 
-                // Hold ctrl, alt or shift to move finely.
-                ui.input(|input| {
-                    if input.modifiers.ctrl || input.modifiers.shift || input.modifiers.alt {
-                        delta *= KNOB_FINE_DRAG_RATIO;
-                    }
-                });
+    /// Makes the knob part of `group`, so dragging it with the group's modifiers held moves
+    /// the other knobs in the group too. The knob needs an id from [`Knob::with_id`] that's
+    /// one of the group's.
+    pub fn with_group(mut self, group: &'a KnobGroup) -> Self {
+        self.group = Some(group);
+        self
+    }
 
-                let step = if let Some(step) = self.step {
-                    // Normalise step size.
-                    step / (max - min).abs()
-                } else {
-                    0.005
-                };
-                let mut new_value =
-                    normalised_from_value(self.value, self.range.clone(), &self.spec)
-                        - delta * step;
-                if self.step.is_some() {
-                    let steps = (new_value / step).round();
-                    new_value = (steps * step).clamp(0.0, 1.0)
-                }
+    /// Makes the knob selectable as part of `selection`, so it can be edited together with
+    /// the other selected knobs. The knob needs an id from [`Knob::with_id`].
+    pub fn with_selection(mut self, selection: &'a KnobSelection) -> Self {
+        self.selection = Some(selection);
+        self
+    }
 
+    /// While `modifiers` are held over the knob, a faint indicator and the label preview the
+    /// value at the pointer's angle. Clicking commits the previewed value, anything else
+    /// leaves the value alone.
+    ///
+    /// This lets sensitive parameters be explored without changing them. The modifiers
+    /// shouldn't match the ones given to [`Knob::with_drag_and_drop`].
+    pub fn with_hover_preview(mut self, modifiers: Modifiers) -> Self {
+        self.hover_preview = Some(modifiers);
+        self
+    }
+
+    /// Whether pressing the upper half of the knob steps the value up, and the lower half steps
+    /// it down, repeating faster and faster while held.
+    ///
+    /// This is a mouse-only alternative to dragging for stepped parameters. Moving the pointer
+    /// turns the press into a normal drag. The default is OFF.
+    pub fn hold_to_nudge(mut self, hold_to_nudge: bool) -> Self {
+        self.hold_to_nudge = hold_to_nudge;
+        self
+    }
+
+    /// Whether clicking latches the knob to the pointer, so the value follows vertical pointer
+    /// movement without holding the button, until the next click or escape.
+    ///
+    /// This helps users with limited hand strength and trackpad users. Dragging still works as
+    /// usual. The default is OFF.
+    pub fn latch(mut self, latch: bool) -> Self {
+        self.latch = latch;
+        self
+    }
+
+    /// Whether hovering the knob shows small up and down arrows at its edge, which step the
+    /// value by one step per click.
+    ///
+    /// This is a discoverable alternative to fine dragging. The default is OFF.
+    pub fn nudge_arrows(mut self, nudge_arrows: bool) -> Self {
+        self.nudge_arrows = nudge_arrows;
+        self
+    }
+
+    /// Whether alt-clicking flips between the current value and the neutral value, to compare
+    /// a setting against the default without losing it.
+    ///
+    /// Needs a neutral value from [`Knob::with_neutral`]. The default is OFF.
+    pub fn flip_to_neutral(mut self, flip_to_neutral: bool) -> Self {
+        self.flip_to_neutral = flip_to_neutral;
+        self
+    }
+
+    /// Whether changes must be confirmed before they're reported, for dangerous parameters.
+    ///
+    /// Changes are staged and shown in the warning color, with a faint indicator at the
+    /// reported value. Clicking the knob or pressing enter confirms them, pressing escape or
+    /// leaving them for a few seconds reverts them. The default is OFF.
+    pub fn with_confirm(mut self, confirm: bool) -> Self {
+        self.confirm = confirm;
+        self
+    }
+
+    /// Whether stylus pressure scales drags, from fine with a light touch to normal speed at
+    /// full pressure.
+    ///
+    /// Only touch input that reports a force is affected, so mice and fingers drag as usual.
+    /// The default is OFF.
+    pub fn pen_pressure(mut self, pen_pressure: bool) -> Self {
+        self.pen_pressure = pen_pressure;
+        self
+    }
+
+    /// Whether fast drags of a stepped knob skip several steps at a time, in proportion to the
+    /// pointer's speed, while slow drags move one step at a time. The default is OFF.
+    pub fn velocity_stepping(mut self, velocity_stepping: bool) -> Self {
+        self.velocity_stepping = velocity_stepping;
+        self
+    }
+
+    /// Whether drags are recorded as `(time, value)` pairs, returned in
+    /// [`KnobResponse::gesture`] when the drag stops. Use it to record automation.
+    /// The default is OFF.
+    pub fn record_gestures(mut self, record_gestures: bool) -> Self {
+        self.record_gestures = record_gestures;
+        self
+    }
+
+    /// Long pressing the knob on a touch screen opens a magnified copy of it, `size` points
+    /// across, above the finger. Dragging is finer while it's open, and it closes on release.
+    pub fn with_long_press_magnifier(mut self, size: f32) -> Self {
+        self.magnifier_size = Some(size);
+        self
+    }
+
+    /// Lets the value be dragged onto other knobs by holding `modifiers` when the drag starts.
+    ///
+    /// The dropped value is carried over in normalised space, so it lands at the same position
+    /// on knobs with different ranges. Only knobs with drag-and-drop turned on accept drops. The
+    /// payload is a [`KnobPayload`], which apps can accept in their own widgets too.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// # let mut value = 0.5;
+    /// Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper)
+    ///     .with_drag_and_drop(egui::Modifiers::COMMAND | egui::Modifiers::SHIFT);
+    /// ```
+    pub fn with_drag_and_drop(mut self, modifiers: Modifiers) -> Self {
+        self.drag_and_drop = Some(modifiers);
+        self
+    }
+
+    /// Whether the knob remembers its value in egui's persisted memory.
+    ///
+    /// The value is restored on the knob's first frame if the bound value is NaN or the neutral
+    /// value, i.e. the app hasn't loaded a value of its own. Give the knob a stable id with
+    /// [`Knob::with_id`], and enable egui's `persistence` feature (e.g. through eframe) for the
+    /// value to survive restarts. The default is OFF.
+    pub fn persist(mut self, persist: bool) -> Self {
+        self.persist = persist;
+        self
+    }
+
+    /// Sets the step size for value changes.
+    ///
+    /// When set, the value will snap to discrete steps as the knob is dragged.
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    /// Named values, e.g. `("Fast", 5.0)` and `("Slow", 250.0)`, offered in a dropdown opened
+    /// from a small disclosure arrow beside the knob. Choosing one sets the knob, and the label
+    /// shows the name while the value matches it.
+    pub fn with_named_values(mut self, named_values: &'a [(&'a str, f32)]) -> Self {
+        self.named_values = named_values;
+        self
+    }
+
+    /// Whether stepped knobs show a small badge with the current step and the number of steps,
+    /// e.g. "7/16", as on pattern length and division selectors. The default is OFF.
+    pub fn step_badge(mut self, step_badge: bool) -> Self {
+        self.step_badge = step_badge;
+        self
+    }
+
+    /// Sets a finer step size used while `modifiers` are held, e.g. a coarse step of `1.0` from
+    /// [`Knob::with_step`] and a fine step of `0.01`, as on lab instruments.
+    ///
+    /// Dragging moves one step per point in either mode, instead of slowing down with the fine
+    /// drag modifiers. With the default label format, the label shows as many decimals as the
+    /// active step needs.
+    pub fn with_fine_step(mut self, step: f32, modifiers: Modifiers) -> Self {
+        self.fine_step = Some((step, modifiers));
+        self
+    }
+
+    /// Sets the neutral value.
+    ///
+    /// When the knob is double clicked, it will reset to the neutral value.
+    pub fn with_neutral(mut self, neutral: f32) -> Self {
+        self.neutral = Some(neutral);
+        self
+    }
+
+    pub fn enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Whether the knob shows its value without letting the user change it, e.g. while it's
+    /// overridden by automation.
+    ///
+    /// Unlike a disabled knob, it's drawn at full brightness. Hovering shows a lock, and
+    /// attempts to change it are reported as [`KnobEvent::EditBlocked`]. The default is OFF.
+    pub fn read_only(mut self, read_only: bool) -> Self {
+        self.read_only = read_only;
+        self
+    }
+
+    /// Whether a disabled knob is drawn with desaturated, faded colors.
+    /// The default is ON. Turn it off to render the given colors exactly.
+    pub fn dim_when_disabled(mut self, dim_when_disabled: bool) -> Self {
+        self.dim_when_disabled = dim_when_disabled;
+        self
+    }
+
+    /// Sets the durations and easing of the knob's animated transitions.
+    ///
+    /// Use [`KnobAnimations::NONE`] to disable all animation, or [`set_reduced_motion`] to
+    /// disable it for every knob.
+    pub fn with_animations(mut self, animations: KnobAnimations) -> Self {
+        self.animations = animations;
+        self
+    }
+
+    /// Whether the intermediate values of the reset sweep are reported to the setter.
+    ///
+    /// When ON, double clicking moves the value towards neutral over [`KnobAnimations::reset`],
+    /// calling the setter every frame. The default is OFF: the setter is called once with the
+    /// neutral value and only the indicator sweeps.
+    pub fn emit_reset_sweep(mut self, emit_reset_sweep: bool) -> Self {
+        self.emit_reset_sweep = emit_reset_sweep;
+        self
+    }
+
+    /// Puts the knob in MIDI-learn mode.
+    ///
+    /// While in learn mode the value can't be changed. Instead, clicking the knob calls `on_arm`
+    /// with the new armed state, and an armed knob is drawn with a pulsing ring. Leave out this
+    /// call once learning is over.
+    pub fn with_learn(mut self, armed: bool, on_arm: impl FnMut(bool) + 'a) -> Self {
+        self.learn = Some(Learn {
+            armed,
+            on_arm: Box::new(on_arm),
+        });
+        self
+    }
+
+    /// Lets the user redefine the knob's range, e.g. to calibrate hardware controls.
+    ///
+    /// With `modifiers` held, double clicking near either end of the arc calls `on_calibrate`
+    /// with a range that has that end moved to the current value. Pass the new range to the
+    /// knob from then on.
+    pub fn with_calibration(
+        mut self,
+        modifiers: Modifiers,
+        on_calibrate: impl FnMut(RangeInclusive<f32>) + 'a,
+    ) -> Self {
+        self.calibration = Some(Calibration {
+            modifiers,
+            on_calibrate: Box::new(on_calibrate),
+        });
+        self
+    }
+
+    /// Shows a small badge with the automation mode of the knob's parameter.
+    pub fn with_automation_state(mut self, state: AutomationState) -> Self {
+        self.automation_state = Some(state);
+        self
+    }
+
+    /// Shows `value`, e.g. from automation playback, as a ghost indicator in `color`.
+    ///
+    /// Pass the current playback value every frame while it plays. The knob's own value can
+    /// still be dragged independently.
+    pub fn with_playback(mut self, value: f32, color: Color32) -> Self {
+        self.playback = Some((value, color));
+        self
+    }
+
+    /// Shows a live `value` fed back from hardware, e.g. a servo's measured position, as a
+    /// second indicator in `color`. The knob's own value is the target, and stays draggable.
+    ///
+    /// Pass the latest value every frame; it's shown as is, without smoothing.
+    pub fn with_actual(mut self, value: f32, color: Color32) -> Self {
+        self.actual = Some((value, color));
+        self
+    }
+
+    /// Highlights the arc between the target and the value from [`Knob::with_actual`] in
+    /// `color` while they're more than `tolerance` apart, e.g. when a motor lags or stalls.
+    pub fn with_deviation_highlight(mut self, tolerance: f32, color: Color32) -> Self {
+        self.deviation = Some((tolerance, color));
+        self
+    }
+
+    /// Draws a miniature curve inside the knob, e.g. the envelope shape or response curve the
+    /// knob controls. `curve` maps `t` from `0.0` to `1.0` across the knob to a height from
+    /// `0.0` to `1.0`; build it from the current value so the curve follows the knob.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// let mut exponent = 2.0;
+    /// let shape = exponent;
+    /// Knob::new(exponent, |v| exponent = v, 0.25..=4.0, KnobStyle::Dot)
+    ///     .with_curve(move |t| t.powf(shape));
+    /// ```
+    pub fn with_curve(mut self, curve: impl Fn(f32) -> f32 + 'a) -> Self {
+        self.curve = Some(Curve::Function(Box::new(curve)));
+        self
+    }
+
+    /// Like [`Knob::with_curve`], but drawing a line through `[x, y]` points, each from `0.0`
+    /// to `1.0`.
+    pub fn with_curve_points(mut self, points: &'a [[f32; 2]]) -> Self {
+        self.curve = Some(Curve::Points(points));
+        self
+    }
+
+    /// Sets the on-screen size, in physical pixels, below which the knob is drawn as a plain
+    /// circle and dot, without its label, ring colors, badges or effects.
+    ///
+    /// This keeps dense, zoomed out views fast and legible. The default is 16 pixels; use `0.0`
+    /// to always draw full detail.
+    pub fn with_min_detail_size(mut self, pixels: f32) -> Self {
+        self.min_detail_size = pixels;
+        self
+    }
+
+    /// Shows a compact numeric drag field instead of the knob when less than `width` points are
+    /// available, e.g. in a collapsed side panel.
+    ///
+    /// The field uses the knob's range, step and label format. The default is to always show the
+    /// knob.
+    pub fn with_fallback_width(mut self, width: f32) -> Self {
+        self.fallback_width = Some(width);
+        self
+    }
+
+    /// Uses a layout computed ahead of time, instead of measuring the label every frame.
+    ///
+    /// The layout's knob size, label position and label offset take precedence over the ones
+    /// set on the knob.
+    pub fn with_layout(mut self, layout: KnobLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Gives the knob a stable id, instead of one derived from its position in the [`Ui`].
+    ///
+    /// This is the id that [`KnobMidiMap`] and [`KnobHandle`] use to route values to the knob.
+    pub fn with_id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Draws a debug overlay with the knob's bounding rects, hit area, normalised value and
+    /// indicator angle.
+    ///
+    /// The default is OFF, or ON with the `extra_debug` feature. Use [`set_debug_overlay`] to
+    /// turn it on for every knob.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Make this a logarithmic knob.
+    /// The default is OFF.
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.spec.logarithmic = logarithmic;
+        self
+    }
+
+    /// For logarithmic knobs that include zero.
+    /// What is the smallest possible value that can be selected
+    /// before the value goes to zero.
+    /// Value is absolute so works for ranges `0..=x` and `x..=0`.
+    pub fn smallest_finite(mut self, smallest_finite: f32) -> Self {
+        self.spec.smallest_finite = smallest_finite.abs();
+        self
+    }
+
+    /// For logarithmic knobs that go to infinity.
+    /// What is the largest possible value that can be selected
+    /// before the value goes to infinity.
+    /// Value is absolute so works for ranges `NEG_INFINITY..=x` and `x..=NEG_INFINITY`.
+    pub fn largest_finite(mut self, largest_finite: f32) -> Self {
+        self.spec.largest_finite = largest_finite.abs();
+        self
+    }
+}
+
+impl Knob<'static, fn(f32)> {
+    /// Moves the knob with `id` to `normalised` on its next frame, as if by a [`KnobHandle`].
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::Knob;
+    /// # let ctx = egui::Context::default();
+    /// Knob::set_normalised(&ctx, "cutoff", 0.25);
+    /// ```
+    pub fn set_normalised(ctx: &egui::Context, id: impl Into<Id>, normalised: f32) {
+        KnobHandle::new(ctx, id).set_normalised(normalised);
+    }
+
+    /// Moves the focused knob with a gamepad's analog stick, deflected by `-1.0..=1.0`.
+    ///
+    /// The knob moves at a rate proportional to the deflection, with a small dead zone. Call
+    /// this whenever the stick moves, including with `0.0` when it's released. The D-pad is
+    /// expected to arrive as arrow keys: up and down step the focused knob, while left and
+    /// right move the focus as usual.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::Knob;
+    /// # let ctx = egui::Context::default();
+    /// # let stick_y = 0.5;
+    /// Knob::set_stick_deflection(&ctx, stick_y);
+    /// ```
+    pub fn set_stick_deflection(ctx: &egui::Context, deflection: f32) {
+        let deflection = deflection.clamp(-1.0, 1.0);
+        let deflection = if deflection.abs() < STICK_DEAD_ZONE {
+            0.0
+        } else {
+            deflection.signum() * (deflection.abs() - STICK_DEAD_ZONE) / (1.0 - STICK_DEAD_ZONE)
+        };
+        state::set_stick(ctx, deflection);
+    }
+}
+
+impl<F: FnMut(f32)> Knob<'_, F> {
+    /// Parses typed or pasted `text` into a value, not yet clamped to the range.
+    fn parse_text(&self, text: &str) -> Option<f32> {
+        let parsed = match &self.value_parser {
+            Some(parse) => parse(text),
+            None => label::parse_leading_number(text),
+        };
+        parsed.filter(|value| !value.is_nan())
+    }
+
+    /// Sets the value to an entered `new_value`, returning whether it changed.
+    fn apply_entered(&mut self, new_value: f32, pass: &mut Pass) -> bool {
+        let new_value = self.clamp(new_value);
+        if new_value == self.value {
+            return false;
+        }
+        self.change_value(new_value, pass);
+        true
+    }
+
+    /// Paints the magnified knob above `knob_rect` while a long press holds it open.
+    fn paint_magnifier(&mut self, ui: &Ui, state: &KnobState, knob_rect: Rect, id: Id) {
+        let Some(size) = self.magnifier_size.filter(|_| state.magnified) else {
+            return;
+        };
+        let radius = size * 0.5;
+        // Above the knob, where the finger doesn't cover it.
+        let screen = ui.ctx().screen_rect();
+        let center = egui::pos2(
+            knob_rect.center().x,
+            knob_rect.top() - radius - self.stroke_width * 4.0,
+        );
+        let margin = Vec2::splat(radius.min(screen.width() * 0.5).min(screen.height() * 0.5));
+        let center = center.clamp(screen.min + margin, screen.max - margin);
+        let painter = ui
+            .ctx()
+            .layer_painter(LayerId::new(egui::Order::Foreground, id.with("magnifier")));
+        let visuals = ui.visuals();
+        painter.circle(center, radius, visuals.window_fill, visuals.window_stroke);
+        let ring_radius = radius * 0.85;
+        let stroke_width = self.stroke_width * size / knob_rect.width().max(1.0);
+        painter.circle_stroke(
+            center,
+            ring_radius,
+            Stroke::new(stroke_width, self.knob_dragging_color),
+        );
+        let normalised = self.normalised(self.value);
+        let angle = TAU * (normalised * self.motion + self.start_turn());
+        let tip = center + Vec2::angled(angle) * (ring_radius * 0.7);
+        match self.style {
+            KnobStyle::Wiper => {
+                painter.line_segment(
+                    [center, tip],
+                    Stroke::new(stroke_width * 1.5, self.line_color),
+                );
+            }
+            KnobStyle::Dot => {
+                painter.circle_filled(tip, stroke_width * 1.5, self.line_color);
+            }
+            KnobStyle::Needle => {
+                painter.extend(needle_shapes(
+                    center,
+                    angle,
+                    ring_radius * 0.85,
+                    stroke_width * 1.5,
+                    self.line_color,
+                ));
+            }
+        }
+        painter.text(
+            center + Vec2::new(0.0, ring_radius * 0.45),
+            Align2::CENTER_CENTER,
+            self.label_format.label_text("", self.value),
+            egui::FontId::proportional(
+                (self.font_size * size / knob_rect.width().max(1.0)).min(radius * 0.3),
+            ),
+            self.text_color,
+        );
+    }
+
+    /// Shows the typed value entry over the knob while it's open.
+    ///
+    /// Enter sets the value, escape or clicking elsewhere cancels.
+    fn value_entry_ui(&mut self, ui: &mut Ui, pass: &mut Pass, knob_rect: Rect) {
+        let Some(text) = &mut pass.buffers.editing else {
+            return;
+        };
+        let edit_id = pass.response.id.with("edit");
+        let rect = Rect::from_center_size(
+            knob_rect.center(),
+            Vec2::new(knob_rect.width().max(48.0), self.font_size + 8.0),
+        );
+        let edit = ui.put(
+            rect,
+            TextEdit::singleline(text)
+                .id(edit_id)
+                .font(egui::FontId::proportional(self.font_size))
+                .horizontal_align(egui::Align::Center),
+        );
+        if edit.lost_focus() {
+            let text = pass.buffers.editing.take().unwrap_or_default();
+            if ui.input(|input| input.key_pressed(egui::Key::Enter))
+                && let Some(value) = self.parse_text(&text)
+            {
+                pass.events.push(KnobEvent::EditSubmitted { value });
+                self.apply_entered(value, pass);
+            }
+            ui.memory_mut(|memory| memory.request_focus(pass.response.id));
+        }
+    }
+
+    /// Reports `new_value` to the setter and records the change.
+    fn change_value(&mut self, new_value: f32, pass: &mut Pass) {
+        if self.confirm {
+            // Reported once confirmed.
+            self.value = new_value;
+        } else {
+            self.report_value(new_value, pass);
+        }
+    }
+
+    /// Returns the knob to `neutral`, sweeping there when `animate` is set.
+    ///
+    /// With [`Knob::emit_reset_sweep`] the value itself sweeps over the following frames,
+    /// otherwise it's set now and only the indicator sweeps.
+    fn reset_to_neutral(&mut self, ui: &Ui, pass: &mut Pass, neutral: f32, animate: bool) {
+        pass.events.push(KnobEvent::ResetToNeutral);
+        let from = self.normalised(self.value);
+        let to = self.normalised(neutral);
+        if animate && self.emit_reset_sweep && !self.animations.reset.is_zero() {
+            pass.state.value_sweep = Some(ValueSweep::new(
+                ui.ctx(),
+                from,
+                to,
+                neutral,
+                self.animations.reset,
+            ));
+            return;
+        }
+        if animate {
+            pass.state.position_sweep = Some(Sweep::new(ui.ctx(), from, to, self.animations.reset));
+        }
+        self.change_value(neutral, pass);
+        pass.announce = true;
+    }
+
+    /// The group a drag of the knob with `id` moves: the selection when the knob is selected
+    /// along with others, otherwise its [`KnobGroup`].
+    fn drag_group(&self, ctx: &egui::Context, id: Id) -> Option<Cow<'_, KnobGroup>> {
+        if let Some(selection) = self.selection {
+            let selected = selection.selected(ctx);
+            if selected.len() > 1 && selected.contains(&id) {
+                return Some(Cow::Owned(selection.group(ctx)));
+            }
+        }
+        self.group.map(Cow::Borrowed)
+    }
+
+    /// The name of `value`, if it matches one of [`Knob::with_named_values`] up to float error.
+    fn name_of(&self, value: f32) -> Option<&str> {
+        let position = self.normalised(value);
+        self.named_values
+            .iter()
+            .find(|&&(_, named)| same_position(self.normalised(named), position))
+            .map(|&(name, _)| name)
+    }
+
+    /// The label text naming `value`, if it matches one of [`Knob::with_named_values`].
+    fn named_value_text(&self, label: &str, value: f32) -> Option<String> {
+        let name = self.name_of(value)?;
+        Some(if label.is_empty() {
+            name.to_owned()
+        } else {
+            format!("{label}: {name}")
+        })
+    }
+
+    /// A [`Knob::two_line_label`] laying out `text`, the name and its line break up to
+    /// `value_start` and the value after it, each line aligned by `halign`.
+    fn two_line_job(&self, text: String, value_start: usize, halign: egui::Align) -> LayoutJob {
+        let section = |byte_range, font_size, color| LayoutSection {
+            leading_space: 0.0,
+            byte_range,
+            format: egui::TextFormat::simple(egui::FontId::proportional(font_size), color),
+        };
+        let sections = vec![
+            section(0..value_start, self.font_size, self.text_color),
+            section(
+                value_start..text.len(),
+                self.value_font_size.unwrap_or(self.font_size),
+                self.value_color.unwrap_or(self.text_color),
+            ),
+        ];
+        LayoutJob {
+            text,
+            sections,
+            halign,
+            ..Default::default()
+        }
+    }
+
+    /// The normalised position of `value`.
+    fn normalised(&self, value: f32) -> f32 {
+        normalised_from_value(value, self.range.clone(), &self.spec)
+    }
+
+    /// The value at normalised position `position`.
+    fn value_at(&self, position: f32) -> f32 {
+        value_from_normalised(position, self.range.clone(), &self.spec)
+    }
+
+    /// The step in normalised units, if the knob has one.
+    fn normalised_step(&self) -> Option<f32> {
+        self.step
+            .map(|step| normalised_step(step, self.range.clone()))
+    }
+
+    /// The lowest and highest values in the range, whichever way round it goes.
+    fn bounds(&self) -> (f32, f32) {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        (min.min(max), min.max(max))
+    }
+
+    /// Brings `value` into the range.
+    fn clamp(&self, value: f32) -> f32 {
+        let (low, high) = self.bounds();
+        value.clamp(low, high)
+    }
+
+    /// The value `direction` steps up, or down for negative `direction`, snapped to the step.
+    fn nudged_value(&self, direction: f32) -> f32 {
+        let step = self.normalised_step().unwrap_or(0.01);
+        let position = self.normalised(self.value);
+        let mut position = self.limit(position + direction * step);
+        if self.step.is_some() {
+            position = snap_normalised(position, step);
+        }
+        self.value_at(position)
+    }
+
+    /// Where the range of motion starts, in turns clockwise from pointing right.
+    fn start_turn(&self) -> f32 {
+        start_turn(self.motion) + self.rotation
+    }
+
+    /// Brings a normalised position back into the range, wrapping around if enabled.
+    fn limit(&self, position: f32) -> f32 {
+        if self.wrap {
+            position.rem_euclid(1.0)
+        } else {
+            position.clamp(0.0, 1.0)
+        }
+    }
+
+    /// Stages a change away from the reported value, then confirms or reverts it.
+    fn update_staged(&mut self, ui: &Ui, pass: &mut Pass) {
+        let reported = pass.reported;
+        if self.value == reported {
+            pass.state.staged = None;
+            return;
+        }
+        let time = ui.input(|input| input.time);
+        // Clicks and key presses that made the change don't confirm it.
+        let changed_now = pass
+            .state
+            .staged
+            .is_none_or(|staged| staged.value != self.value);
+        if changed_now {
+            pass.state.staged = Some(Staged {
+                value: self.value,
+                time,
+            });
+        }
+        let staged_time = pass.state.staged.map_or(time, |staged| staged.time);
+        let response = &pass.response;
+        let confirmed = !changed_now
+            && !response.dragged()
+            && (response.clicked()
+                || (response.has_focus()
+                    && ui.input_mut(|input| input.consume_key(Modifiers::NONE, egui::Key::Enter))));
+        let cancelled = ((response.has_focus() || response.lost_focus() || response.hovered())
+            && ui.input(|input| input.key_pressed(egui::Key::Escape)))
+            || time - staged_time >= CONFIRM_TIMEOUT;
+        if confirmed {
+            let staged = self.value;
+            self.value = reported;
+            self.report_value(staged, pass);
+            pass.state.staged = None;
+        } else if cancelled {
+            self.value = reported;
+            pass.state.staged = None;
+        } else {
+            ui.ctx().request_repaint_after(Duration::from_secs_f64(
+                staged_time + CONFIRM_TIMEOUT - time,
+            ));
+        }
+    }
+
+    /// Calls the setter with `new_value` and records the change.
+    fn report_value(&mut self, new_value: f32, pass: &mut Pass) {
+        (self.set_value)(new_value);
+        pass.events.push(KnobEvent::ValueChanged {
+            old: self.value,
+            new: new_value,
+        });
+        self.value = new_value;
+        pass.response.mark_changed();
+    }
+
+    /// Number of detents between `from` and `to`: step boundaries, or the neutral value for
+    /// knobs without a step.
+    fn detents_between(&self, from: f32, to: f32) -> u32 {
+        if let Some(step) = self.normalised_step() {
+            let index = |value| (self.normalised(value) / step).round();
+            (index(to) - index(from)).abs() as u32
+        } else if let Some(neutral) = self.neutral {
+            // Landing on neutral counts, leaving it doesn't.
+            let side = |value: f32| (value - neutral).signum();
+            u32::from(to == neutral || (from != neutral && side(from) != side(to)))
+        } else {
+            0
+        }
+    }
+
+    /// Shows the knob as a [`DragValue`], see [`Knob::with_fallback_width`].
+    fn drag_value_ui(mut self, ui: &mut Ui) -> Response {
+        let (min, max) = (*self.range.start(), *self.range.end());
+        let (low, high) = (min.min(max), min.max(max));
+        let mut value = self.value;
+        // Match the knob's drag speed.
+        let speed = if (high - low).is_finite() {
+            (high - low) * 0.005
+        } else {
+            value.abs().max(1.0) * 0.01
+        };
+        let label_format = RefCell::new(&mut self.label_format);
+        let drag_value = DragValue::new(&mut value)
+            .range(low..=high)
+            .speed(self.step.unwrap_or(speed))
+            .custom_formatter(|value, _| label_format.borrow_mut().label_text("", value as f32));
+        let mut response = ui.add_enabled(self.enabled, drag_value);
+        if let Some(label) = &self.label {
+            response = response.on_hover_text(label.as_ref());
+        }
+
+        if let Some(step) = self.step
+            && low.is_finite()
+        {
+            value = (low + ((value - low) / step).round() * step).clamp(low, high);
+        }
+        if value != self.value {
+            (self.set_value)(value);
+            response.mark_changed();
+        }
+        response
+    }
+
+    /// Clamps a value outside the range, unless it's to be shown as it is.
+    fn clamp_out_of_range(&mut self) {
+        let (low, high) = self.bounds();
+        let out_of_range = self.value < low || self.value > high;
+        match self.out_of_range {
+            OutOfRange::Clamp if out_of_range => self.value = self.value.clamp(low, high),
+            OutOfRange::Warn if out_of_range => {
+                log::warn!(
+                    "Knob value {} is outside its range {low}..={high}, showing it clamped",
+                    self.value
+                );
+                self.value = self.value.clamp(low, high);
+            }
+            _ => {}
+        }
+    }
+
+    /// Switches to the fine step while its modifiers are held, returning it if so.
+    fn apply_fine_step(&mut self, ui: &Ui) -> Option<f32> {
+        let fine_step = self
+            .fine_step
+            .filter(|&(_, modifiers)| ui.input(|input| input.modifiers.contains(modifiers)))
+            .map(|(step, _)| step);
+        if self.fine_step.is_some() {
+            self.step = fine_step.or(self.step);
+            if let (LabelFormat::Default, Some(step)) = (&self.label_format, self.step) {
+                self.label_format = LabelFormat::Decimals(label::step_decimals(step));
+            }
+        }
+        fine_step
+    }
+
+    /// Measures the knob and the widest text its label will show.
+    fn measure(&mut self, ui: &Ui) -> KnobLayout {
+        let max = *self.range.end();
+        match (self.layout, &self.label) {
+            (Some(layout), _) => layout,
+            // Without a label there is no text to measure.
+            (None, None) => KnobLayout {
+                knob_size: self.size,
+                label_size: None,
+                label_position: self.label_position,
+                label_offset: self.label_offset,
+                label_padding: self.label_padding,
+            },
+            (None, Some(label)) => {
+                let mut max_text =
+                    self.label_format
+                        .display_text(label, max, 1.0, self.value_display);
+                // Names can be longer than any number, so make room for them too.
+                for &(_, value) in self.named_values {
+                    if let Some(text) = self.named_value_text(label, value)
+                        && text.chars().count() > max_text.chars().count()
+                    {
+                        max_text = text;
+                    }
+                }
+                let mut layout = ui.fonts(|fonts| {
+                    KnobLayout::compute(
+                        self.size,
+                        Some(&max_text),
+                        self.font_size,
+                        self.label_position,
+                        self.label_offset,
+                        fonts,
+                    )
+                });
+                layout.label_padding = self.label_padding;
+                if self.two_line_label {
+                    let mut text = String::with_capacity(label.len() + 16);
+                    text.push_str(label);
+                    text.push('\n');
+                    let value_start = text.len();
+                    self.label_format
+                        .write_display(max, 1.0, self.value_display, &mut text);
+                    let value_chars = text[value_start..].chars().count();
+                    if let Some(&(name, _)) = self
+                        .named_values
+                        .iter()
+                        .max_by_key(|(name, _)| name.chars().count())
+                        && name.chars().count() > value_chars
+                    {
+                        text.truncate(value_start);
+                        text.push_str(name);
+                    }
+                    let job = self.two_line_job(text, value_start, egui::Align::Min);
+                    layout.label_size = Some(ui.fonts(|fonts| fonts.layout_job(job).size()));
+                }
+                if self.label_swatch.is_some()
+                    && let Some(label_size) = &mut layout.label_size
+                {
+                    label_size.x += self.font_size;
+                }
+                layout
+            }
+        }
+    }
+
+    /// Space to add above and below the knob in a horizontal row, so its center, or its label's
+    /// baseline, is where the row centers its widgets.
+    fn inline_padding(
+        &self,
+        ui: &Ui,
+        layout: &KnobLayout,
+        outer_size: Vec2,
+        frame_top: f32,
+        rotated_label: bool,
+    ) -> (f32, f32) {
+        if self.inline_align == InlineAlign::Whole || !ui.layout().is_horizontal() {
+            return (0.0, 0.0);
+        }
+        let above = self.margin
+            + frame_top
+            + layout
+                .knob_rect(Rect::from_min_size(egui::Pos2::ZERO, layout.size()))
+                .center()
+                .y;
+        let below = outer_size.y + self.margin * 2.0 - above;
+        let side_label = matches!(
+            layout.label_position,
+            LabelPosition::Left | LabelPosition::Right
+        ) && layout.label_size.is_some()
+            && !rotated_label;
+        // How far below the row's center the knob's center goes.
+        let offset = if self.inline_align == InlineAlign::Baseline && side_label {
+            let body = egui::TextStyle::Body.resolve(ui.style());
+            let label = egui::FontId::proportional(self.font_size);
+            ui.fonts(|fonts| {
+                layout::baseline_below_center(fonts, body)
+                    - layout::baseline_below_center(fonts, label)
+            })
+        } else {
+            0.0
+        };
+        let difference = offset * 2.0 - above + below;
+        (difference.max(0.0), (-difference).max(0.0))
+    }
+
+    /// Lays the knob out in the space the parent offers, and allocates it.
+    fn place(&mut self, ui: &mut Ui) -> Placement {
+        let right_to_left = ui.layout().prefer_right_to_left();
+        self.label_position = self.label_position.resolve(right_to_left);
+        let mut layout = self.measure(ui);
+
+        if let Some(max_size) = self.max_size {
+            layout.knob_size = layout.knob_size.min(max_size);
+        }
+
+        let frame_margin = self
+            .frame
+            .map(|frame| frame.total_margin())
+            .unwrap_or_default();
+        // The width the parent layout offers: the whole row in wrapping layouts, where the knob
+        // can move to the next row, or what's left of it otherwise.
+        let offered_width = if ui.layout().main_wrap {
+            ui.max_rect().width()
+        } else {
+            ui.available_width()
+        } - frame_margin.sum().x;
+        let turned = |size: Vec2| Vec2::new(size.y, size.x);
+        if layout.label_position == LabelPosition::Auto {
+            let mut beside = layout;
+            if self.rotated_label {
+                beside.label_size = beside.label_size.map(turned);
+            }
+            layout.label_position = beside.auto_position(offered_width);
+        }
+        layout.label_position = layout.label_position.resolve(right_to_left);
+        let rotated_label = self.rotated_label
+            && matches!(
+                layout.label_position,
+                LabelPosition::Left | LabelPosition::Right
+            );
+        if rotated_label && self.layout.is_none() {
+            layout.label_size = layout.label_size.map(turned);
+        }
+        // Narrow a long label to fit.
+        let fit_label = !rotated_label && !self.two_line_label && self.layout.is_none();
+        let mut label_truncated = fit_label && layout.fit_width(offered_width);
+        // If that's not enough, shrink the knob itself, down to its minimum size.
+        let overflow = layout.size().x - offered_width;
+        if let Some(min_size) = self.min_size
+            && overflow > 0.0
+            && layout.knob_size > min_size
+        {
+            layout.knob_size = (layout.knob_size - overflow).max(min_size);
+            label_truncated |= fit_label && layout.fit_width(offered_width);
+        }
+        self.size = layout.knob_size;
+
+        let outer_size = layout.size() + frame_margin.sum();
+        // The margin goes above and below within the allocation, so it never becomes a gap to
+        // the side, or a place to wrap, in horizontal layouts.
+        let margin = Vec2::new(0.0, self.margin);
+        let (pad_above, pad_below) =
+            self.inline_padding(ui, &layout, outer_size, frame_margin.top, rotated_label);
+        let (auto_id, space) =
+            ui.allocate_space(outer_size + margin * 2.0 + Vec2::new(0.0, pad_above + pad_below));
+        let space = Rect::from_min_max(
+            space.min + margin + Vec2::new(0.0, pad_above),
+            space.max - margin - Vec2::new(0.0, pad_below),
+        );
+        // Justified layouts offer more than the knob needs: center it in what's given.
+        let outer_rect = ui.layout().align_size_within_rect(outer_size, space);
+        Placement {
+            layout,
+            outer_rect,
+            rect: outer_rect - frame_margin,
+            auto_id,
+            right_to_left,
+            rotated_label,
+            label_truncated,
+        }
+    }
+
+    /// Starts the frame's interaction: senses the pointer and loads the knob's state.
+    fn begin(&mut self, ui: &mut Ui, placement: &Placement) -> Pass {
+        let hit_rect = placement.outer_rect.expand(self.hit_padding);
+        let id = self.id.unwrap_or(placement.auto_id);
+        let response = ui.interact(hit_rect, id, Sense::click_and_drag());
+        let state_id = KnobState::id(response.id);
+        let state = KnobState::load(ui.ctx(), state_id);
+        let buffers = KnobBuffers::take(ui.ctx(), state_id);
+        let mut pass = Pass {
+            // Detect changes made from outside the knob since the last frame.
+            externally_changed: state.last_value.is_some_and(|last| last != self.value),
+            response,
+            state_id,
+            state,
+            buffers,
+            events: SmallVec::new(),
+            reported: self.value,
+            read_only: false,
+            sweeping: false,
+            announce: false,
+            linked: false,
+            drag_delta: 0.0,
+            detents_crossed: 0,
+            drag_total: 0.0,
+            hit_min: false,
+            hit_max: false,
+            gesture: None,
+            arrows: None,
+            disclosure: None,
+            preview: None,
+        };
+
+        // A staged change is shown instead of the reported value, unless the value was changed
+        // from outside in the meantime.
+        if !self.confirm || pass.externally_changed {
+            pass.state.staged = None;
+        }
+        if let Some(staged) = pass.state.staged {
+            self.value = staged.value;
+        }
+
+        let persisted_id = pass.response.id.with("persisted");
+        if self.persist
+            && pass.state.last_value.is_none()
+            && (self.value.is_nan() || Some(self.value) == self.neutral)
+            && let Some(stored) = ui.data_mut(|data| data.get_persisted::<f32>(persisted_id))
+        {
+            let stored = self.clamp(stored);
+            if stored != self.value {
+                self.change_value(stored, &mut pass);
+            }
+        }
+
+        // A read-only knob handles input like a disabled one, but is drawn like an enabled one.
+        pass.read_only = self.read_only && self.enabled;
+        if pass.read_only {
+            self.enabled = false;
+            let key_pressed = pass.response.has_focus()
+                && ui.input(|input| {
+                    [egui::Key::Enter, egui::Key::ArrowUp, egui::Key::ArrowDown]
+                        .iter()
+                        .any(|&key| input.key_pressed(key))
+                });
+            if pass.response.drag_started() || pass.response.clicked() || key_pressed {
+                pass.events.push(KnobEvent::EditBlocked);
+            }
+            if pass.response.hovered() {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::NotAllowed);
+            }
+        }
+        pass
+    }
+
+    /// Handles clicks, double clicks and drags on the knob itself, and steps a reset sweep.
+    fn handle_pointer(
+        &mut self,
+        ui: &Ui,
+        pass: &mut Pass,
+        placement: &Placement,
+        fine_step: Option<f32>,
+    ) {
+        if !self.latch || !self.enabled || self.learn.is_some() {
+            pass.state.latched = false;
+        }
+        if let Some(learn) = &mut self.learn {
+            if self.enabled && pass.response.clicked() {
+                (learn.on_arm)(!learn.armed);
+            }
+            return;
+        }
+        if !self.enabled {
+            return;
+        }
+        let response = &pass.response;
+        if pass.state.latched {
+            pass.state.latched = !ui.input(|input| {
+                input.pointer.primary_clicked() || input.key_pressed(egui::Key::Escape)
+            });
+        } else {
+            pass.state.latched = self.latch && response.clicked();
+        }
+        let calibrating = response.double_clicked()
+            && self.calibration.as_ref().is_some_and(|calibration| {
+                ui.input(|input| input.modifiers.contains(calibration.modifiers))
+            });
+        if calibrating {
+            // Double click near an end of the arc to move that end to the current value.
+            let knob_center = placement.knob_rect().center();
+            let near_start = response.interact_pointer_pos().is_some_and(|pointer| {
+                position_at_angle(
+                    (pointer - knob_center).angle(),
+                    self.start_turn(),
+                    self.motion,
+                ) < 0.5
+            });
+            let range = if near_start {
+                self.value..=*self.range.end()
+            } else {
+                *self.range.start()..=self.value
+            };
+            if let Some(calibration) = &mut self.calibration {
+                (calibration.on_calibrate)(range);
+            }
+        } else if response.double_clicked() {
+            // Double click to return to neutral state.
+            if let Some(neutral) = self.neutral
+                && neutral != self.value
+            {
+                self.reset_to_neutral(ui, pass, neutral, true);
+            }
+        } else if response.dragged()
+            && (pass.state.carrying
+                || (pass.state.drag_start.is_none()
+                    && self.drag_and_drop.is_some_and(|modifiers| {
+                        ui.input(|input| input.modifiers.contains(modifiers))
+                    })))
+        {
+            let position = self.normalised(self.value);
+            pass.state.carrying = true;
+            pass.state.drag_start = Some(position);
+            response.dnd_set_drag_payload(KnobPayload {
+                source: response.id,
+                value: self.value,
+                normalised: position,
+            });
+        } else if response.dragged() || pass.state.latched {
+            self.drag(ui, pass, placement, fine_step);
+        } else if let Some(step) = animation::step_value_sweep(
+            ui.ctx(),
+            &mut pass.state.value_sweep,
+            self.animations.easing,
+        ) {
+            let new_value = match step {
+                ValueSweepStep::Moving(position) => {
+                    pass.sweeping = true;
+                    self.value_at(position)
+                }
+                ValueSweepStep::Finished(value) => {
+                    pass.announce = true;
+                    value
+                }
+            };
+            if new_value != self.value {
+                self.change_value(new_value, pass);
+            }
+        }
+    }
+
+    /// Moves the value with a drag, or while the knob is latched to the pointer.
+    fn drag(&mut self, ui: &Ui, pass: &mut Pass, placement: &Placement, fine_step: Option<f32>) {
+        let state = &mut pass.state;
+        // Grabbing the knob interrupts a reset sweep.
+        state.value_sweep = None;
+
+        if pass.response.drag_started() || state.drag_start.is_none() {
+            state.drag_start = Some(self.normalised(self.value));
+            state.drag_start_value = Some(self.value);
+            pass.events
+                .push(KnobEvent::DragStarted { value: self.value });
+            state.drag_distance = 0.0;
+            state.pen_pressure = None;
+            state.group_drag = false;
+            pass.buffers.gesture = self
+                .record_gestures
+                .then(|| vec![(ui.input(|input| input.time), self.value)]);
+            if let Some(group) = self.drag_group(ui.ctx(), pass.response.id)
+                && group.contains(pass.response.id)
+                && ui.input(|input| input.modifiers.contains(group.modifiers()))
+            {
+                group.begin_drag(ui.ctx(), pass.response.id, state.drag_start.unwrap_or(0.0));
+                state.group_drag = true;
+            }
+        }
+
+        // Switching between the coarse and fine step carries on from the current value.
+        if state.fine_step != fine_step.is_some() {
+            state.fine_step = fine_step.is_some();
+            state.drag_start = Some(self.normalised(self.value));
+            state.drag_distance = 0.0;
+        }
+
+        let mut delta = if state.latched {
+            ui.input(|input| input.pointer.delta().y)
+        } else {
+            pass.response.drag_delta().y
+        };
+
+        if state.magnified
+            && let Some(size) = self.magnifier_size
+        {
+            let knob_size = placement.layout.knob_size;
+            delta *= knob_size / size.max(knob_size);
+        }
+
+        if self.pen_pressure {
+            let force = ui.input(|input| {
+                input.events.iter().rev().find_map(|event| match event {
+                    egui::Event::Touch { force, .. } => *force,
+                    _ => None,
+                })
+            });
+            if let Some(force) = force {
+                state.pen_pressure = Some(force.clamp(0.0, 1.0));
+            }
+            if let Some(pressure) = state.pen_pressure {
+                delta *= egui::lerp(KNOB_FINE_DRAG_RATIO..=1.0, pressure);
+            }
+        }
+
+        // Hold the coarse modifiers to move quickly, or ctrl, alt or shift to move finely, unless
+        // they move the group.
+        ui.input(|input| {
+            if self
+                .coarse_drag
+                .is_some_and(|modifiers| input.modifiers.contains(modifiers))
+            {
+                delta *= KNOB_COARSE_DRAG_RATIO;
+            } else if !state.group_drag
+                && fine_step.is_none()
+                && (input.modifiers.ctrl || input.modifiers.shift || input.modifiers.alt)
+            {
+                delta *= KNOB_FINE_DRAG_RATIO;
+            } else if self.velocity_stepping && self.step.is_some() {
+                let speed = input.pointer.velocity().y.abs();
+                delta *= (1.0 + (speed - VELOCITY_STEP_THRESHOLD).max(0.0) / VELOCITY_STEP_SCALE)
+                    .min(VELOCITY_STEP_MAX);
+            }
+        });
+        state.drag_distance += delta;
+
+        let step = self.normalised_step().unwrap_or(0.005);
+        // Measuring from the start of the drag lets fine drags accumulate across
+        // frames, even when each frame's movement is smaller than a step.
+        let mut position = state.drag_start.unwrap_or_default() - state.drag_distance * step;
+        let absolute_pointer = self
+            .absolute_drag
+            .filter(|&modifiers| ui.input(|input| input.modifiers.contains(modifiers)))
+            .and_then(|_| pass.response.interact_pointer_pos());
+        if let Some(pointer) = absolute_pointer {
+            let knob_center = placement.knob_rect().center();
+            position = position_at_angle(
+                (pointer - knob_center).angle(),
+                self.start_turn(),
+                self.motion,
+            );
+            // Relative dragging carries on from here once the modifier is released.
+            state.drag_start = Some(position);
+            state.drag_distance = 0.0;
+        }
+        let overshoot = if self.wrap {
+            0.0
+        } else {
+            position - position.clamp(0.0, 1.0)
+        };
+        pass.hit_min = overshoot < 0.0 && state.overshoot >= 0.0;
+        pass.hit_max = overshoot > 0.0 && state.overshoot <= 0.0;
+        state.overshoot = overshoot;
+        position = self.limit(position);
+        if self.step.is_some() {
+            position = snap_normalised(position, step);
+        }
+        let new_value = self.value_at(position);
+
+        if new_value != self.value {
+            pass.drag_delta = new_value - self.value;
+            pass.detents_crossed = self.detents_between(self.value, new_value);
+            self.change_value(new_value, pass);
+            if pass.state.group_drag
+                && let Some(group) = self.drag_group(ui.ctx(), pass.response.id)
+            {
+                group.drag(ui.ctx(), pass.response.id, position);
+            }
+            if let Some(gesture) = &mut pass.buffers.gesture {
+                gesture.push((ui.input(|input| input.time), self.value));
+            }
+        }
+    }
+
+    /// Handles the controls around the knob: the magnifier, nudge arrows, named values, hold to
+    /// nudge, flipping to neutral, selection, reset requests and the hover preview.
+    fn handle_controls(&mut self, ui: &Ui, pass: &mut Pass, placement: &Placement) {
+        let active = self.enabled && self.learn.is_none();
+        if self.magnifier_size.is_some() && active {
+            if pass.response.long_touched() {
+                pass.state.magnified = true;
+            }
+            if !pass.response.is_pointer_button_down_on() {
+                pass.state.magnified = false;
+            }
+        } else {
+            pass.state.magnified = false;
+        }
+        let knob_rect = placement.knob_rect();
+        // Up and down arrows, shown while the knob or one of the arrows is hovered.
+        if self.nudge_arrows && active {
+            let arrow_size = (knob_rect.width() * 0.2).max(6.0);
+            let x = knob_rect.center().x + knob_rect.width() * 0.5 * 0.75;
+            let up_rect = Rect::from_center_size(
+                egui::pos2(x, knob_rect.center().y - arrow_size * 0.6),
+                Vec2::splat(arrow_size),
+            );
+            let down_rect = up_rect.translate(Vec2::new(0.0, arrow_size * 1.2));
+            let id = pass.response.id;
+            let up = ui.interact(up_rect, id.with("nudge_up"), Sense::click());
+            let down = ui.interact(down_rect, id.with("nudge_down"), Sense::click());
+            for (arrow, direction) in [(&up, 1.0), (&down, -1.0)] {
+                if arrow.clicked() {
+                    let new_value = self.nudged_value(direction);
+                    if new_value != self.value {
+                        self.change_value(new_value, pass);
+                        pass.announce = true;
+                    }
+                }
+            }
+            if pass.response.hovered() || up.hovered() || down.hovered() {
+                pass.arrows = Some([(up_rect, up.hovered()), (down_rect, down.hovered())]);
+            }
+        }
+        // A disclosure arrow opposite the nudge arrows, opening a list of the named values.
+        if !self.named_values.is_empty() && active {
+            let arrow_size = (knob_rect.width() * 0.2).max(6.0);
+            let arrow_rect = Rect::from_center_size(
+                egui::pos2(
+                    knob_rect.center().x - knob_rect.width() * 0.5 * 0.75,
+                    knob_rect.center().y,
+                ),
+                Vec2::splat(arrow_size),
+            );
+            let arrow_id = pass.response.id.with("named_values");
+            let arrow = ui.interact(arrow_rect, arrow_id, Sense::click());
+            let chosen = compat::popup_below(ui, arrow.id.with("popup"), &arrow, |ui| {
+                let mut chosen = None;
+                for &(name, value) in self.named_values {
+                    let text = format!("{name} = {}", self.label_format.label_text("", value));
+                    let selected =
+                        same_position(self.normalised(value), self.normalised(self.value));
+                    if ui.selectable_label(selected, text).clicked() {
+                        chosen = Some(value);
+                    }
+                }
+                chosen
+            })
+            .flatten();
+            if let Some(value) = chosen
+                && value != self.value
+            {
+                self.change_value(value, pass);
+                pass.announce = true;
+            }
+            pass.disclosure = Some((arrow_rect, arrow.hovered()));
+        }
+        if self.hold_to_nudge && active {
+            let time = ui.input(|input| input.time);
+            let response = &pass.response;
+            if !response.is_pointer_button_down_on() || response.drag_delta() != Vec2::ZERO {
+                pass.state.nudge = None;
+            } else if pass.state.nudge.is_none()
+                && ui.input(|input| input.pointer.primary_pressed())
+                && let Some(pointer) = response.interact_pointer_pos()
+            {
+                let above = pointer.y < knob_rect.center().y;
+                pass.state.nudge = Some(Nudge {
+                    direction: if above { 1.0 } else { -1.0 },
+                    next_time: time,
+                    repeats: 0,
+                });
+            }
+            if let Some(mut nudge) = pass.state.nudge {
+                if time >= nudge.next_time {
+                    let new_value = self.nudged_value(nudge.direction);
+                    if new_value != self.value {
+                        self.change_value(new_value, pass);
+                    }
+                    nudge.next_time = time
+                        + if nudge.repeats == 0 {
+                            NUDGE_DELAY
+                        } else {
+                            (NUDGE_INTERVAL * NUDGE_ACCELERATION.powi(nudge.repeats))
+                                .max(NUDGE_MIN_INTERVAL)
+                        };
+                    nudge.repeats += 1;
+                }
+                ui.ctx().request_repaint_after(Duration::from_secs_f64(
+                    (nudge.next_time - time).max(0.0),
+                ));
+                pass.state.nudge = Some(nudge);
+            }
+        }
+        // The second click of a double click resets instead.
+        if self.flip_to_neutral
+            && active
+            && pass.response.clicked()
+            && !pass.response.double_clicked()
+            && ui.input(|input| input.modifiers.alt)
+            && let Some(neutral) = self.neutral
+        {
+            if self.value != neutral {
+                pass.state.flipped_from = Some(self.value);
+                self.reset_to_neutral(ui, pass, neutral, false);
+            } else if let Some(flipped_from) = pass.state.flipped_from.take() {
+                self.change_value(flipped_from, pass);
+                pass.announce = true;
+            }
+        }
+        if let Some(selection) = self.selection
+            && active
+        {
+            let response = &pass.response;
+            if response.clicked() {
+                let toggle = ui.input(|input| input.modifiers.command);
+                selection.click(ui.ctx(), response.id, toggle);
+            }
+            let delete = ui.input(|input| {
+                input.key_pressed(egui::Key::Delete) || input.key_pressed(egui::Key::Backspace)
+            });
+            if delete
+                && (response.has_focus()
+                    || (response.hovered() && ui.memory(|memory| memory.focused().is_none())))
+                && selection.is_selected(ui.ctx(), response.id)
+            {
+                selection.reset(ui.ctx());
+            }
+        }
+        if state::take_reset(ui.ctx(), pass.response.id)
+            && self.enabled
+            && let Some(neutral) = self.neutral
+            && neutral != self.value
+        {
+            self.reset_to_neutral(ui, pass, neutral, false);
+        }
+        if active
+            && !pass.response.dragged()
+            && let Some(modifiers) = self.hover_preview
+            && ui.input(|input| input.modifiers.contains(modifiers))
+            && let Some(pointer) = pass.response.hover_pos()
+        {
+            let mut position = position_at_angle(
+                (pointer - knob_rect.center()).angle(),
+                self.start_turn(),
+                self.motion,
+            );
+            if let Some(step) = self.normalised_step() {
+                position = snap_normalised(position, step);
+            }
+            let value = self.value_at(position);
+            if pass.response.clicked() && value != self.value {
+                self.change_value(value, pass);
+                pass.announce = true;
+            }
+            pass.preview = Some((position, value));
+        }
+    }
+
+    /// Handles the arrow keys and gamepad of a focused knob, and requests from assistive
+    /// technology.
+    fn handle_keys(&mut self, ui: &Ui, pass: &mut Pass) {
+        let active = self.enabled && self.learn.is_none();
+        // Gamepad navigation: the D-pad arrives as arrow keys, the stick with `set_stick_deflection`.
+        if active && pass.response.has_focus() && pass.buffers.editing.is_none() {
+            ui.memory_mut(|memory| {
+                memory.set_focus_lock_filter(
+                    pass.response.id,
+                    EventFilter {
+                        vertical_arrows: true,
+                        ..Default::default()
+                    },
+                );
+            });
+            let steps = ui.input_mut(|input| {
+                input.count_and_consume_key(Modifiers::NONE, egui::Key::ArrowUp) as f32
+                    - input.count_and_consume_key(Modifiers::NONE, egui::Key::ArrowDown) as f32
+            });
+            let stick = state::stick(ui.ctx());
+            let step = self.normalised_step();
+            let position = self.normalised(self.value);
+            let target = if steps != 0.0 {
+                pass.state.stick_position = None;
+                Some(position + steps * step.unwrap_or(0.01))
+            } else if stick != 0.0 {
+                let dt = ui.input(|input| input.stable_dt).min(0.1);
+                let target = self.limit(
+                    pass.state.stick_position.unwrap_or(position) + stick * STICK_SPEED * dt,
+                );
+                pass.state.stick_position = Some(target);
+                ui.ctx().request_repaint();
+                Some(target)
+            } else {
+                pass.state.stick_position = None;
+                None
+            };
+            if let Some(target) = target {
+                let mut target = self.limit(target);
+                if let Some(step) = step {
+                    target = snap_normalised(target, step);
+                }
+                let new_value = self.value_at(target);
                 if new_value != self.value {
-                    (self.set_value)(value_from_normalised(
-                        new_value,
-                        self.range.clone(),
-                        &self.spec,
-                    ));
-                    response.mark_changed();
+                    self.change_value(new_value, pass);
+                    // The stick changes the value every frame, too often to announce.
+                    pass.announce |= steps != 0.0;
                 }
             }
+        } else {
+            pass.state.stick_position = None;
+        }
+        if active {
+            let requests = accessibility::requests(ui, pass.response.id);
+            let new_value = if let Some(value) = requests.set_value {
+                Some(self.clamp(value))
+            } else if requests.steps != 0 {
+                // Stepped and wrapped like the arrow keys.
+                Some(self.nudged_value(requests.steps as f32))
+            } else {
+                None
+            };
+            if let Some(new_value) = new_value
+                && new_value != self.value
+            {
+                self.change_value(new_value, pass);
+                pass.announce = true;
+            }
         }
+    }
 
-        let is_dragging = response.dragged() && self.enabled;
-        let painter = ui.painter();
-        let knob_rect = match self.label_position {
-            LabelPosition::Left => {
-                Rect::from_min_size(rect.right_top() + Vec2::new(-knob_size.x, 0.0), knob_size)
+    /// Takes values from outside the knob: queued by a [`KnobHandle`] or [`KnobMidiMap`], from
+    /// the other knob of a link, or dropped on it from another knob.
+    fn handle_external(&mut self, ui: &Ui, pass: &mut Pass) {
+        // The user's own drag takes precedence.
+        if let Some(queued) = state::take_queued(ui.ctx(), pass.response.id)
+            && self.enabled
+            && !pass.response.dragged()
+        {
+            let mut position = queued.normalised.clamp(0.0, 1.0);
+            if let Some(step) = self.normalised_step() {
+                position = snap_normalised(position, step);
             }
-            LabelPosition::Right => Rect::from_min_size(rect.left_top(), knob_size),
-            LabelPosition::Top => Rect::from_min_size(
-                rect.left_bottom() + Vec2::new((rect.width() - knob_size.x) / 2.0, -knob_size.y),
-                knob_size,
-            ),
-            LabelPosition::Bottom => Rect::from_min_size(
-                rect.left_top() + Vec2::new((rect.width() - knob_size.x) / 2.0, 0.0),
-                knob_size,
-            ),
+            let new_value = self.value_at(position);
+            if let Some(changes_id) = queued.group_changes {
+                group::record_change(
+                    ui.ctx(),
+                    changes_id,
+                    pass.response.id,
+                    self.normalised(new_value),
+                );
+            }
+            if queued.morph.is_zero() || !animation::motion_allowed(ui) {
+                pass.state.value_sweep = None;
+                if new_value != self.value {
+                    self.change_value(new_value, pass);
+                    pass.externally_changed = true;
+                }
+            } else {
+                let from = self.normalised(self.value);
+                pass.state.value_sweep = Some(ValueSweep::new(
+                    ui.ctx(),
+                    from,
+                    position,
+                    new_value,
+                    queued.morph,
+                ));
+                ui.ctx().request_repaint();
+            }
+        }
+        // Values from the other knob of a link aren't passed back to it.
+        if let Some(value) = state::take_queued_value(ui.ctx(), pass.response.id)
+            && self.enabled
+            && !pass.response.dragged()
+        {
+            let new_value = self.clamp(value);
+            if new_value != self.value {
+                self.change_value(new_value, pass);
+                pass.externally_changed = true;
+                pass.linked = true;
+            }
+        }
+        if self.enabled
+            && self.learn.is_none()
+            && self.drag_and_drop.is_some()
+            && let Some(payload) = pass.response.dnd_release_payload::<KnobPayload>()
+            && payload.source != pass.response.id
+        {
+            let mut position = payload.normalised.clamp(0.0, 1.0);
+            if let Some(step) = self.normalised_step() {
+                position = snap_normalised(position, step);
+            }
+            let new_value = self.value_at(position);
+            if new_value != self.value {
+                self.change_value(new_value, pass);
+                pass.announce = true;
+            }
+        }
+    }
+
+    /// Handles the clipboard, randomizing, and opening the typed value entry.
+    fn handle_commands(&mut self, ui: &Ui, pass: &mut Pass) {
+        let active = self.enabled && self.learn.is_none();
+        if let Some(clipboard) = self.clipboard
+            && (pass.response.has_focus()
+                || (pass.response.hovered() && ui.memory(|memory| memory.focused().is_none())))
+        {
+            let (copy, paste) = ui.input(|input| {
+                let copy = input
+                    .events
+                    .iter()
+                    .any(|event| matches!(event, egui::Event::Copy));
+                let paste = input.events.iter().rev().find_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                });
+                (copy, paste)
+            });
+            if copy {
+                let text = match clipboard {
+                    ClipboardValue::Formatted => self.label_format.label_text("", self.value),
+                    ClipboardValue::Raw => self.value.to_string(),
+                };
+                ui.ctx().copy_text(text);
+            }
+            if let Some(text) = paste
+                && active
+                && let Some(value) = self.parse_text(&text)
+            {
+                pass.events.push(KnobEvent::Pasted { value });
+                pass.announce |= self.apply_entered(value, pass);
+            }
+        }
+        if let Some(mut random) = self.random.take()
+            && active
+        {
+            let mut roll = pass.response.has_focus()
+                && ui.input_mut(|input| input.consume_key(Modifiers::NONE, egui::Key::R));
+            pass.response.context_menu(|ui| {
+                if ui.button("Randomize").clicked() {
+                    roll = true;
+                    compat::close_menu(ui);
+                }
+            });
+            if roll {
+                let mut position = random().clamp(0.0, 1.0);
+                if let Some(step) = self.normalised_step() {
+                    position = snap_normalised(position, step);
+                }
+                let new_value = self.value_at(position);
+                if new_value != self.value {
+                    self.change_value(new_value, pass);
+                    pass.announce = true;
+                }
+            }
+        }
+        // Press enter or start typing a number on a focused knob to type in a value.
+        if active
+            && pass.response.has_focus()
+            && pass.buffers.editing.is_none()
+            && pass.state.staged.is_none()
+        {
+            let typing = ui.input(|input| {
+                input.events.iter().any(|event| {
+                    matches!(event, egui::Event::Text(text)
+                        if text.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '.'))
+                })
+            });
+            let enter = !typing
+                && ui.input_mut(|input| input.consume_key(Modifiers::NONE, egui::Key::Enter));
+            if typing || enter {
+                // Enter selects the current value for editing. Typed text goes to the editor,
+                // which is shown later this frame, so it replaces the value.
+                let text = if enter {
+                    self.label_format.label_text("", self.value)
+                } else {
+                    String::new()
+                };
+                let edit_id = pass.response.id.with("edit");
+                let mut edit_state = TextEditState::load(ui.ctx(), edit_id).unwrap_or_default();
+                edit_state.cursor.set_char_range(Some(CCursorRange::two(
+                    CCursor::new(0),
+                    CCursor::new(text.chars().count()),
+                )));
+                edit_state.store(ui.ctx(), edit_id);
+                ui.memory_mut(|memory| memory.request_focus(edit_id));
+                pass.buffers.editing = Some(text);
+            }
+        }
+    }
+
+    /// Describes the knob to screen readers, [`KnobInteractions`] and its tooltip.
+    fn describe(&mut self, ui: &Ui, pass: &mut Pass) {
+        accessibility::describe(
+            ui,
+            pass.response.id,
+            self.range.clone(),
+            self.step,
+            self.value,
+            pass.announce,
+        );
+        let editing = pass.buffers.editing.is_some();
+        if pass.response.dragged() || editing {
+            let label = self.label.as_deref().unwrap_or("");
+            let normalised = self.normalised(self.value);
+            let text = self.named_value_text(label, self.value).unwrap_or_else(|| {
+                self.label_format
+                    .display_text(label, self.value, normalised, self.value_display)
+            });
+            KnobInteractions::record(
+                ui.ctx(),
+                KnobInteraction {
+                    id: pass.response.id,
+                    label: self.label.as_deref().map(str::to_owned),
+                    value: self.value,
+                    text,
+                    editing,
+                },
+            );
+        }
+        let enabled = self.enabled;
+        let value = self.value;
+        let value_display = self.value_display;
+        let normalised = self.normalised(value);
+        // The formatter needs `&mut`, but egui only takes `Fn` and only calls it when needed.
+        let label_format = RefCell::new(&mut self.label_format);
+        pass.response.widget_info(|| {
+            let mut info =
+                WidgetInfo::slider(enabled, value as f64, self.label.as_deref().unwrap_or(""));
+            // Read out the value the way it is displayed, e.g. "Volume, 42%".
+            info.current_text_value = Some(label_format.borrow_mut().display_text(
+                "",
+                value,
+                normalised,
+                value_display,
+            ));
+            info
+        });
+        if let Some(tooltip) = self.tooltip.take() {
+            let info = KnobInfo {
+                value,
+                normalised,
+                text: self.label_format.label_text("", value),
+                range: self.range.clone(),
+                neutral: self.neutral,
+                modified: self.neutral.is_some_and(|neutral| neutral != value),
+            };
+            pass.response = pass.response.clone().on_hover_ui(|ui| tooltip(ui, &info));
+        } else if let Some(coarse_drag) = self.coarse_drag {
+            let is_mac = matches!(
+                ui.ctx().os(),
+                egui::os::OperatingSystem::Mac | egui::os::OperatingSystem::IOS
+            );
+            let coarse = egui::ModifierNames::NAMES.format(&coarse_drag, is_mac);
+            pass.response = pass.response.clone().on_hover_text(format!(
+                "Hold Ctrl, Shift or Alt to drag finely, {coarse} to drag coarsely"
+            ));
+        }
+    }
+
+    /// Ends the drag when released, settles staged and linked values, and remembers the value
+    /// for the next frame.
+    fn update_state(&mut self, ui: &Ui, pass: &mut Pass) {
+        let response = &pass.response;
+        let state = &mut pass.state;
+        // Keep the drag to ourselves, from the press on, before it passes the drag threshold.
+        // On touch screens and the web, the same gesture also arrives as scrolling, which would
+        // pan a containing ScrollArea or the page.
+        let interacting = response.dragged()
+            || state.latched
+            || response.is_pointer_button_down_on()
+            || (self.lock_scroll && (response.hovered() || pass.buffers.editing.is_some()));
+        if interacting && self.enabled {
+            ui.input_mut(|input| {
+                input.smooth_scroll_delta = Vec2::ZERO;
+                input.raw_scroll_delta = Vec2::ZERO;
+            });
+        }
+        if (response.dragged() || state.latched) && self.enabled {
+            ui.ctx().set_cursor_icon(if state.carrying {
+                egui::CursorIcon::Grabbing
+            } else {
+                egui::CursorIcon::ResizeVertical
+            });
+        }
+        // Also reported on the frame the drag stops.
+        pass.drag_total = match state.drag_start_value {
+            Some(start) if !state.carrying => self.value - start,
+            _ => 0.0,
         };
+        if !response.dragged() && !state.latched {
+            if state.drag_start_value.is_some() && !state.carrying {
+                pass.events.push(KnobEvent::DragEnded { value: self.value });
+            }
+            pass.gesture = pass.buffers.gesture.take();
+            state.drag_start = None;
+            state.drag_start_value = None;
+            state.carrying = false;
+            if state.group_drag
+                && let Some(group) = self.drag_group(ui.ctx(), response.id)
+            {
+                group.end_drag(ui.ctx());
+            }
+            state.group_drag = false;
+            state.overshoot = 0.0;
+        }
+        if self.confirm {
+            self.update_staged(ui, pass);
+        }
+        let previous = pass.reported;
+        if pass.state.staged.is_none() {
+            pass.reported = self.value;
+        }
+        let reported = pass.reported;
+        if let Some(link) = self.link
+            && reported != previous
+            && !pass.linked
+            && !ui.input(|input| input.modifiers.contains(link.break_modifiers()))
+        {
+            link.update(ui.ctx(), pass.response.id, reported);
+        }
+        if self.persist && !reported.is_nan() && pass.state.last_value != Some(reported) {
+            let persisted_id = pass.response.id.with("persisted");
+            ui.data_mut(|data| data.insert_persisted(persisted_id, reported));
+        }
+        pass.state.last_value = Some(reported);
+        pass.state.last_position = Some(self.normalised(reported));
+    }
 
-        let center = knob_rect.center();
-        let radius = if is_dragging {
-            knob_size.x * 0.55
+    /// Paints the knob, returning where it was drawn.
+    fn paint(&mut self, ui: &mut Ui, pass: &mut Pass, placement: &Placement) -> KnobGeometry {
+        let response = &pass.response;
+        let is_dragging =
+            (response.dragged() || pass.state.latched) && self.enabled && self.learn.is_none();
+        let hover_t = animation::animate_bool(
+            ui.ctx(),
+            response.id.with("hover"),
+            response.hovered() && self.enabled && !pass.read_only,
+            self.animations.hover,
+            self.animations.easing,
+        );
+        let drag_t = animation::animate_bool(
+            ui.ctx(),
+            response.id.with("drag"),
+            is_dragging,
+            self.animations.drag_pop,
+            self.animations.easing,
+        );
+        let flash_t = if self.flash_color.is_some() {
+            animation::flash(
+                ui.ctx(),
+                &mut pass.state.flash_start,
+                pass.externally_changed,
+                self.animations.flash,
+                self.animations.easing,
+            )
         } else {
-            knob_size.x * 0.5
+            0.0
         };
+        let mut painter = ui.painter().clone();
+        if let Some(layer_id) = self.layer_id {
+            painter = painter.with_layer_id(layer_id);
+        }
 
-        // The range of motion of the knob. 1.0 means a full rotation.
-        let range = 0.85;
-
-        // 0.0 points right. 0.25 points down.
-        let down = 0.25;
+        if let Some(mut frame) = self.frame {
+            if !self.enabled && self.dim_when_disabled {
+                frame.fill = dimmed(frame.fill);
+                frame.stroke.color = dimmed(frame.stroke.color);
+            }
+            painter.add(frame.paint(placement.rect));
+        }
+        let knob_rect = placement.knob_rect();
 
-        // The necessary offset from pointing down, in order for motion to be symmetrical.
-        let offset = (1.0 - range) * 0.5;
+        // Snapped to physical pixels, so outlines stay crisp at any scale and zoom.
+        let pixels_per_point = ui.ctx().pixels_per_point();
+        let center = knob_rect.center().round_to_pixels(pixels_per_point);
+        self.stroke_width = self
+            .stroke_width
+            .round_to_pixels(pixels_per_point)
+            .max(1.0 / pixels_per_point);
+        let radius = placement.layout.knob_size * (0.5 + 0.05 * drag_t);
 
-        let start_angle = down + offset;
+        let start_angle = self.start_turn();
+        let normalised = self.normalised(self.value);
+        // The user's own drags are shown immediately, everything else is smoothed.
+        let smoothing = if is_dragging || pass.sweeping {
+            Duration::ZERO
+        } else {
+            self.animations.smoothing
+        };
+        let displayed = animation::displayed_position(
+            ui.ctx(),
+            &mut pass.state.position_sweep,
+            normalised,
+            smoothing,
+            self.animations.easing,
+        );
+        // Pushing past either end stretches the indicator a little, springing back on release.
+        let overshoot = pass.state.overshoot;
+        let stretch =
+            overshoot.signum() * RUBBER_BAND_MAX * (1.0 - 1.0 / (1.0 + overshoot.abs() * 10.0));
+        let stretch = ui.ctx().animate_value_with_time(
+            response.id.with("stretch"),
+            if overshoot == 0.0 { 0.0 } else { stretch },
+            if animation::motion_allowed(ui) {
+                0.1
+            } else {
+                0.0
+            },
+        );
+        let geometry = KnobGeometry {
+            center,
+            radius,
+            min_angle: TAU * start_angle,
+            max_angle: TAU * (start_angle + self.motion),
+            angle: TAU * ((displayed + stretch) * self.motion + start_angle),
+        };
 
-        let angle =
-            TAU * (normalised_from_value(self.value, self.range, &self.spec) * range + start_angle);
+        let canvas = Canvas {
+            painter,
+            geometry,
+            knob_rect,
+            displayed,
+            // Hovering goes halfway to the dragging color.
+            knob_color: self
+                .knob_color
+                .lerp_to_gamma(self.knob_dragging_color, drag_t.max(hover_t * 0.5)),
+            segments_per_turn: self.arc_quality.segments_per_turn(radius, pixels_per_point),
+            flash_t,
+        };
 
-        let knob_color = if is_dragging {
-            self.knob_dragging_color
+        let debug = self.debug || debug_overlay(ui.ctx());
+        // Too small on screen for details to be legible: draw a plain circle and dot.
+        if self.size * pixels_per_point < self.min_detail_size {
+            canvas.painter.circle_stroke(
+                center,
+                radius,
+                Stroke::new(self.stroke_width, canvas.knob_color),
+            );
+            let dot_pos = center + Vec2::angled(geometry.angle) * (radius * 0.7);
+            canvas
+                .painter
+                .circle_filled(dot_pos, self.stroke_width, self.line_color);
         } else {
-            self.knob_color
+            self.paint_ring(ui, pass, &canvas);
+            self.paint_arcs(&canvas);
+            self.paint_marks(ui, pass, placement, &canvas);
+            self.paint_indicators(ui, pass, &canvas);
+            self.paint_label(pass, placement, &canvas);
+        }
+        if debug {
+            debug::paint_debug_overlay(
+                &canvas.painter,
+                &debug::DebugInfo {
+                    hit_rect: pass.response.rect,
+                    rect: placement.rect,
+                    knob_rect,
+                    normalised: displayed,
+                    angle: geometry.angle,
+                },
+            );
+        }
+        geometry
+    }
+
+    /// Paints the ring around the knob.
+    fn paint_ring(&self, ui: &Ui, pass: &Pass, canvas: &Canvas) {
+        let KnobGeometry { center, radius, .. } = canvas.geometry;
+        let thresholds = match self.ring_color {
+            // Stroked directly, so egui feathers its edges.
+            RingColor::Solid => {
+                canvas.painter.circle_stroke(
+                    center,
+                    radius,
+                    Stroke::new(self.stroke_width, canvas.knob_color),
+                );
+                return;
+            }
+            RingColor::Threshold { value, .. } => [self.normalised(value), 0.0],
+            RingColor::Zones { warn, danger, .. } => {
+                [self.normalised(warn), self.normalised(danger)]
+            }
+            RingColor::Gradient(..) => [0.0; 2],
         };
-        painter.circle_stroke(center, radius, Stroke::new(self.stroke_width, knob_color));
+        canvas.painter.add(ring::cached_ring_mesh(
+            ui.ctx(),
+            pass.response.id.with("ring"),
+            self.ring_color,
+            center,
+            radius,
+            self.stroke_width,
+            canvas.segments_per_turn,
+            self.start_turn(),
+            self.motion,
+            thresholds,
+            canvas.knob_color,
+        ));
+    }
 
-        match self.style {
+    /// Paints the arcs showing the value along the ring: scaled, bipolar and fine readouts.
+    fn paint_arcs(&self, canvas: &Canvas) {
+        let KnobGeometry { center, radius, .. } = canvas.geometry;
+        let (start_angle, range) = (self.start_turn(), self.motion);
+        let displayed = canvas.displayed;
+        if let Some((min_width, max_width)) = self.scaled_arc
+            && displayed > 0.0
+        {
+            let reach = displayed.min(1.0);
+            let mut mesh = egui::Mesh::default();
+            ring::add_tapered_arc(
+                &mut mesh,
+                center,
+                radius,
+                [min_width, egui::lerp(min_width..=max_width, reach)],
+                canvas.segments_per_turn,
+                start_angle,
+                start_angle + range * reach,
+                self.line_color,
+                self.line_color,
+            );
+            canvas.painter.add(mesh);
+        }
+
+        if let Some((negative, positive)) = self.bipolar_colors {
+            let (min, max) = (*self.range.start(), *self.range.end());
+            let origin = self.neutral.map_or(0.5, |neutral| self.normalised(neutral));
+            let reach = displayed.clamp(0.0, 1.0);
+            let color = if self.value < self.neutral.unwrap_or((min + max) * 0.5) {
+                negative
+            } else {
+                positive
+            };
+            let mut mesh = egui::Mesh::default();
+            ring::add_arc(
+                &mut mesh,
+                center,
+                radius,
+                self.stroke_width * 2.0,
+                canvas.segments_per_turn,
+                start_angle + range * origin.min(reach),
+                start_angle + range * origin.max(reach),
+                color,
+                color,
+            );
+            canvas.painter.add(mesh);
+        }
+
+        if let Some(coarse_steps) = self.fine_ring {
+            let position = displayed.clamp(0.0, 1.0);
+            let fine = if position >= 1.0 {
+                1.0
+            } else {
+                (position * coarse_steps as f32).fract()
+            };
+            let width = self.stroke_width * 1.5;
+            let mut mesh = egui::Mesh::default();
+            for (ring_radius, reach) in [
+                (radius + self.stroke_width * 2.0, position),
+                (radius - self.stroke_width * 2.0, fine),
+            ] {
+                ring::add_arc(
+                    &mut mesh,
+                    center,
+                    ring_radius,
+                    width,
+                    canvas.segments_per_turn,
+                    start_angle,
+                    start_angle + range,
+                    self.line_color.gamma_multiply(0.25),
+                    self.line_color.gamma_multiply(0.25),
+                );
+                ring::add_arc(
+                    &mut mesh,
+                    center,
+                    ring_radius,
+                    width,
+                    canvas.segments_per_turn,
+                    start_angle,
+                    start_angle + range * reach,
+                    self.line_color,
+                    self.line_color,
+                );
+            }
+            canvas.painter.add(mesh);
+        }
+    }
+
+    /// Paints the highlights and badges around the knob, and its nudge and disclosure arrows.
+    fn paint_marks(&self, ui: &Ui, pass: &Pass, placement: &Placement, canvas: &Canvas) {
+        let painter = &canvas.painter;
+        let KnobGeometry { center, radius, .. } = canvas.geometry;
+        let knob_rect = canvas.knob_rect;
+        let knob_size = placement.layout.knob_size;
+        let response = &pass.response;
+        if self.learn.as_ref().is_some_and(|learn| learn.armed) {
+            let pulse = if animation::motion_allowed(ui) {
+                animation::request_animation_frame(ui.ctx(), animation::AMBIENT_FRAME);
+                let time = ui.input(|input| input.time) as f32;
+                0.6 + 0.4 * (time * TAU * LEARN_PULSE_RATE).sin()
+            } else {
+                1.0
+            };
+            painter.circle_stroke(
+                center,
+                radius + self.stroke_width * 2.0,
+                Stroke::new(
+                    self.stroke_width,
+                    ui.visuals().warn_fg_color.gamma_multiply(pulse),
+                ),
+            );
+        }
+
+        if self
+            .selection
+            .is_some_and(|selection| selection.is_selected(ui.ctx(), response.id))
+        {
+            painter.circle_stroke(
+                center,
+                radius + self.stroke_width * 2.0,
+                ui.visuals().selection.stroke,
+            );
+        }
+
+        if self.drag_and_drop.is_some()
+            && response
+                .dnd_hover_payload::<KnobPayload>()
+                .is_some_and(|payload| payload.source != response.id)
+        {
+            painter.circle_stroke(
+                center,
+                radius + self.stroke_width * 2.0,
+                ui.visuals().selection.stroke,
+            );
+        }
+
+        if let Some(state) = self.automation_state {
+            let badge_radius = (knob_size * 0.14).max(4.0);
+            let badge_center = knob_rect.right_top() + Vec2::new(-badge_radius, badge_radius);
+            let badge_color = if !self.enabled && self.dim_when_disabled {
+                dimmed(state.color())
+            } else {
+                state.color()
+            };
+            painter.circle_filled(badge_center, badge_radius, badge_color);
+            if badge_radius >= 6.0 {
+                painter.text(
+                    badge_center,
+                    Align2::CENTER_CENTER,
+                    state.letter(),
+                    egui::FontId::proportional(badge_radius * 1.4),
+                    Color32::BLACK,
+                );
+            }
+        }
+
+        if self.step_badge
+            && let Some(step) = self.normalised_step()
+            && step > 0.0
+        {
+            let text = format!(
+                "{}/{}",
+                (self.normalised(self.value) / step).round() as i64 + 1,
+                (1.0 / step).round() as i64 + 1
+            );
+            let galley = painter.layout_no_wrap(
+                text,
+                egui::FontId::proportional((knob_size * 0.2).max(8.0)),
+                self.text_color,
+            );
+            let badge_rect = Align2::LEFT_BOTTOM
+                .anchor_size(knob_rect.left_bottom(), galley.size())
+                .expand2(Vec2::new(2.0, 0.0));
+            painter.rect_filled(
+                badge_rect,
+                badge_rect.height() * 0.5,
+                self.knob_color.gamma_multiply(0.8),
+            );
+            painter.galley(
+                badge_rect.center() - galley.size() * 0.5,
+                galley,
+                self.text_color,
+            );
+        }
+
+        if let Some((arrow_rect, hovered)) = pass.disclosure {
+            let color = if hovered {
+                self.knob_dragging_color
+            } else {
+                self.line_color
+            };
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    arrow_rect.left_top() + Vec2::new(0.0, arrow_rect.height() * 0.25),
+                    arrow_rect.right_top() + Vec2::new(0.0, arrow_rect.height() * 0.25),
+                    arrow_rect.center_bottom() - Vec2::new(0.0, arrow_rect.height() * 0.25),
+                ],
+                color,
+                Stroke::NONE,
+            ));
+        }
+
+        if let Some(arrows) = pass.arrows {
+            for ((arrow_rect, hovered), up) in arrows.into_iter().zip([true, false]) {
+                let color = if hovered {
+                    self.knob_dragging_color
+                } else {
+                    self.line_color
+                };
+                let (tip, base) = if up {
+                    (arrow_rect.center_top(), arrow_rect.bottom())
+                } else {
+                    (arrow_rect.center_bottom(), arrow_rect.top())
+                };
+                painter.add(egui::Shape::convex_polygon(
+                    vec![
+                        tip,
+                        egui::pos2(arrow_rect.right(), base),
+                        egui::pos2(arrow_rect.left(), base),
+                    ],
+                    color,
+                    Stroke::NONE,
+                ));
+            }
+        }
+
+        if pass.read_only && response.hovered() {
+            let lock_size = (knob_size * 0.3).max(8.0);
+            let lock_center = knob_rect.right_bottom() - Vec2::splat(lock_size * 0.5);
+            paint_lock(painter, lock_center, lock_size, self.text_color);
+        }
+
+        if let Some(flash_color) = self.flash_color
+            && canvas.flash_t > 0.0
+        {
+            painter.circle_stroke(
+                center,
+                radius,
+                Stroke::new(
+                    self.stroke_width * 2.0,
+                    flash_color.gamma_multiply(canvas.flash_t),
+                ),
+            );
+        }
+    }
+
+    /// Paints the response curve, dial labels and value inside the knob, and its indicators.
+    fn paint_indicators(&mut self, ui: &Ui, pass: &Pass, canvas: &Canvas) {
+        let painter = &canvas.painter;
+        let geometry = canvas.geometry;
+        let KnobGeometry { center, radius, .. } = geometry;
+        if let Some(curve) = &self.curve {
+            // The largest square inside the ring, a little inset.
+            let curve_rect = Rect::from_center_size(center, Vec2::splat(radius * 1.1));
+            painter.add(egui::Shape::line(
+                curve.points_in(curve_rect),
+                Stroke::new(
+                    self.stroke_width * 0.75,
+                    self.line_color.gamma_multiply(0.6),
+                ),
+            ));
+        }
+
+        if !self.dial_labels.is_empty() {
+            let gaps = if self.motion >= 1.0 {
+                self.dial_labels.len()
+            } else {
+                (self.dial_labels.len() - 1).max(1)
+            };
+            let font_id = egui::FontId::proportional((radius * 0.25).max(6.0));
+            for (i, label) in self.dial_labels.iter().enumerate() {
+                let angle = geometry.angle_at(i as f32 / gaps as f32);
+                painter.text(
+                    center + Vec2::angled(angle) * (radius * 0.75),
+                    Align2::CENTER_CENTER,
+                    *label,
+                    font_id.clone(),
+                    self.text_color,
+                );
+            }
+        }
+        if self.center_value {
+            painter.text(
+                center + Vec2::new(0.0, radius * 0.5),
+                Align2::CENTER_CENTER,
+                self.label_format.label_text("", self.value),
+                egui::FontId::proportional(radius * 0.35),
+                self.text_color,
+            );
+        }
+
+        let paint_single = |angle: f32, color: Color32| match self.style {
             KnobStyle::Wiper => {
                 let pointer = center + Vec2::angled(angle) * (radius * 0.7);
                 painter.line_segment(
                     [center, pointer],
-                    Stroke::new(self.stroke_width * 1.5, self.line_color),
+                    Stroke::new(self.stroke_width * 1.5, color),
                 );
             }
             KnobStyle::Dot => {
                 let dot_pos = center + Vec2::angled(angle) * (radius * 0.7);
-                painter.circle_filled(dot_pos, self.stroke_width * 1.5, self.line_color);
+                painter.circle_filled(dot_pos, self.stroke_width * 1.5, color);
+            }
+            KnobStyle::Needle => {
+                painter.extend(needle_shapes(
+                    center,
+                    angle,
+                    radius * 0.85,
+                    self.stroke_width,
+                    color,
+                ));
+            }
+        };
+        let middle_angle = (geometry.min_angle + geometry.max_angle) * 0.5;
+        let paint_indicator = |angle: f32, color: Color32| {
+            if self.mirrored {
+                let spread = (angle - geometry.min_angle) * 0.5;
+                paint_single(middle_angle - spread, color);
+                paint_single(middle_angle + spread, color);
+            } else {
+                paint_single(angle, color);
             }
+        };
+        if let Some((position, _)) = pass.preview {
+            paint_indicator(
+                geometry.angle_at(position),
+                self.line_color.gamma_multiply(0.4),
+            );
         }
-
-        if let Some(label) = self.label {
-            let value_string = (self.label_format)(self.value);
-            let label_text = if label.is_empty() {
-                // If the label is empty, format only the value string
-                value_string.to_string()
+        if let Some((value, color)) = self.playback {
+            let position = self.normalised(value);
+            let smoothing = if animation::motion_allowed(ui) {
+                PLAYBACK_SMOOTHING
             } else {
-                // If the label is not empty, format with the label, colon, and value string
-                format!("{}: {}", label, value_string)
-            };
-            let font_id = egui::FontId::proportional(self.font_size);
-
-            let (label_pos, alignment) = match self.label_position {
-                LabelPosition::Top => (
-                    Vec2::new(
-                        rect.center().x,
-                        rect.min.y - self.label_offset + label_padding,
-                    ),
-                    Align2::CENTER_TOP,
-                ),
-                LabelPosition::Bottom => (
-                    Vec2::new(rect.center().x, rect.max.y + self.label_offset),
-                    Align2::CENTER_BOTTOM,
-                ),
-                LabelPosition::Left => (
-                    Vec2::new(rect.min.x - self.label_offset, rect.center().y),
-                    // Might be wrong!
-                    Align2::LEFT_CENTER,
-                ),
-                LabelPosition::Right => (
-                    Vec2::new(rect.max.x - label_size.x, rect.center().y),
-                    // Fixed this - it was right_center before which caused alignment issues.
-                    Align2::LEFT_CENTER,
-                ),
+                0.0
             };
-
-            ui.painter().text(
-                label_pos.to_pos2(),
-                alignment,
-                label_text,
-                font_id,
-                self.text_color,
+            let position = ui.ctx().animate_value_with_time(
+                pass.response.id.with("playback"),
+                position,
+                smoothing,
+            );
+            paint_indicator(geometry.angle_at(position), color.gamma_multiply(0.6));
+        }
+        let angle = geometry.angle;
+        if let Some((actual, color)) = self.actual {
+            let actual_angle = geometry.angle_at(self.normalised(actual));
+            if let Some((tolerance, deviation_color)) = self.deviation
+                && (actual - self.value).abs() > tolerance
+            {
+                let mut mesh = egui::Mesh::default();
+                ring::add_arc(
+                    &mut mesh,
+                    center,
+                    radius,
+                    self.stroke_width * 2.0,
+                    canvas.segments_per_turn,
+                    actual_angle.min(angle) / TAU,
+                    actual_angle.max(angle) / TAU,
+                    deviation_color,
+                    deviation_color,
+                );
+                painter.add(mesh);
+            }
+            paint_indicator(actual_angle, color);
+        }
+        if pass.state.staged.is_some() {
+            paint_indicator(
+                geometry.angle_at(self.normalised(pass.reported)),
+                self.line_color.gamma_multiply(0.4),
             );
+            paint_indicator(angle, ui.visuals().warn_fg_color);
+        } else if self.value < self.bounds().0 || self.value > self.bounds().1 {
+            // Only an out-of-range value that's preserved gets here.
+            paint_indicator(angle, ui.visuals().warn_fg_color);
+        } else {
+            paint_indicator(angle, self.line_color);
+        }
+    }
+
+    /// Paints the label, with its swatch and effect.
+    fn paint_label(&mut self, pass: &Pass, placement: &Placement, canvas: &Canvas) {
+        let Some(label) = &self.label else {
+            return;
+        };
+        let painter = &canvas.painter;
+        let layout = &placement.layout;
+        let rect = placement.rect;
+        let (label_value, label_normalised) = pass.preview.map_or(
+            (self.value, self.normalised(self.value)),
+            |(position, value)| (value, position),
+        );
+        // The label and value are written into one string, which egui then takes to lay out.
+        let named = self.name_of(label_value);
+        let mut label_text = String::with_capacity(label.len() + 16);
+        label_text.push_str(label);
+        if self.two_line_label {
+            label_text.push('\n');
+        } else if !label.is_empty() {
+            label_text.push_str(": ");
+        }
+        let value_start = label_text.len();
+        match named {
+            Some(name) => label_text.push_str(name),
+            None => self.label_format.write_display(
+                label_value,
+                label_normalised,
+                self.value_display,
+                &mut label_text,
+            ),
         }
+        let font_id = egui::FontId::proportional(self.font_size);
 
-        if cfg!(feature = "extra_debug") {
-            // Draw the bounding rect
-            painter.rect_stroke(
-                rect,
-                0.0,
-                Stroke::new(1.0, Color32::RED),
-                egui::StrokeKind::Inside,
+        let (label_pos, alignment) = if placement.right_to_left {
+            layout.label_anchor_right_to_left(rect)
+        } else {
+            layout.label_anchor(rect)
+        };
+        let effect_offsets: &[Vec2] = match self.label_effect {
+            LabelEffect::None => &[],
+            LabelEffect::Outline(_) => &[
+                Vec2::new(-1.0, -1.0),
+                Vec2::new(0.0, -1.0),
+                Vec2::new(1.0, -1.0),
+                Vec2::new(-1.0, 0.0),
+                Vec2::new(1.0, 0.0),
+                Vec2::new(-1.0, 1.0),
+                Vec2::new(0.0, 1.0),
+                Vec2::new(1.0, 1.0),
+            ],
+            LabelEffect::Shadow { offset, .. } => &[offset],
+        };
+        let effect_color = match self.label_effect {
+            LabelEffect::None => Color32::TRANSPARENT,
+            LabelEffect::Outline(color) | LabelEffect::Shadow { color, .. } => {
+                if !self.enabled && self.dim_when_disabled {
+                    dimmed(color)
+                } else {
+                    color
+                }
+            }
+        };
+        let swatch_width = if self.label_swatch.is_some() {
+            self.font_size
+        } else {
+            0.0
+        };
+        // Lay out once and reuse the galley for the effect and the text itself.
+        let galley = if self.two_line_label {
+            let halign = if placement.rotated_label {
+                egui::Align::Center
+            } else {
+                alignment.x()
+            };
+            painter.layout_job(self.two_line_job(label_text, value_start, halign))
+        } else if placement.label_truncated {
+            let mut job = LayoutJob::simple_singleline(label_text, font_id, self.text_color);
+            job.wrap = egui::text::TextWrapping::truncate_at_width(
+                layout.label_size.unwrap_or_default().x - swatch_width,
             );
-            painter.rect_stroke(
-                knob_rect,
-                0.0,
-                Stroke::new(1.0, Color32::GREEN),
-                egui::StrokeKind::Inside,
+            painter.layout_job(job)
+        } else {
+            painter.layout_no_wrap(label_text, font_id, self.text_color)
+        };
+        if placement.rotated_label {
+            // The label's strip beside the knob, with the text centered along it.
+            let label_width = layout.label_size.unwrap_or_default().x;
+            let strip = if layout.label_position == LabelPosition::Left {
+                Rect::from_min_max(rect.min, egui::pos2(rect.min.x + label_width, rect.max.y))
+            } else {
+                Rect::from_min_max(egui::pos2(rect.max.x - label_width, rect.min.y), rect.max)
+            };
+            let length = galley.size().x + swatch_width;
+            let thickness = galley.size().y;
+            // Rotation is around the text's top left corner, which ends up bottom left when
+            // reading upwards and top right when reading downwards.
+            let (start, angle) = if layout.label_position == LabelPosition::Left {
+                (
+                    strip.center() + Vec2::new(-thickness, length) * 0.5,
+                    -TAU / 4.0,
+                )
+            } else {
+                (
+                    strip.center() + Vec2::new(thickness, -length) * 0.5,
+                    TAU / 4.0,
+                )
+            };
+            // The directions the text runs in, and of its lines' downwards.
+            let along = Vec2::angled(angle);
+            let across = Vec2::angled(angle + TAU / 4.0);
+            if let Some(color) = self.label_swatch {
+                let color = if !self.enabled && self.dim_when_disabled {
+                    dimmed(color)
+                } else {
+                    color
+                };
+                let swatch = Rect::from_center_size(
+                    start + along * (self.font_size * 0.35) + across * (thickness * 0.5),
+                    Vec2::splat(self.font_size * 0.7),
+                );
+                painter.rect_filled(swatch, 2.0, color);
+            }
+            // Aligned lines put the galley's own origin inside it.
+            let text_start =
+                start + along * (swatch_width - galley.rect.min.x) - across * galley.rect.min.y;
+            for offset in effect_offsets {
+                painter.add(
+                    egui::epaint::TextShape::new(
+                        text_start + *offset,
+                        galley.clone(),
+                        effect_color,
+                    )
+                    .with_override_text_color(effect_color)
+                    .with_angle(angle),
+                );
+            }
+            painter.add(
+                egui::epaint::TextShape::new(text_start, galley, self.text_color).with_angle(angle),
             );
+        } else {
+            let mut label_rect =
+                alignment.anchor_size(label_pos, galley.size() + Vec2::new(swatch_width, 0.0));
+            if let Some(color) = self.label_swatch {
+                let color = if !self.enabled && self.dim_when_disabled {
+                    dimmed(color)
+                } else {
+                    color
+                };
+                let swatch = Rect::from_center_size(
+                    label_rect.left_center() + Vec2::new(self.font_size * 0.35, 0.0),
+                    Vec2::splat(self.font_size * 0.7),
+                );
+                painter.rect_filled(swatch, 2.0, color);
+                label_rect.min.x += swatch_width;
+            }
+            // Aligned lines put the galley's own origin inside it.
+            let text_pos = label_rect.min - galley.rect.min.to_vec2();
+            for offset in effect_offsets {
+                painter.galley_with_override_text_color(
+                    text_pos + *offset,
+                    galley.clone(),
+                    effect_color,
+                );
+            }
+            painter.galley(text_pos, galley, self.text_color);
+        }
+    }
+
+    /// Dims the colors of a disabled knob.
+    fn dim(&mut self) {
+        self.knob_color = dimmed(self.knob_color);
+        self.line_color = dimmed(self.line_color);
+        self.text_color = dimmed(self.text_color);
+        self.value_color = self.value_color.map(dimmed);
+        self.ring_color = self.ring_color.map(dimmed);
+        self.bipolar_colors = self
+            .bipolar_colors
+            .map(|(negative, positive)| (dimmed(negative), dimmed(positive)));
+    }
+
+    /// Shows the knob, returning where it was drawn along with its response.
+    ///
+    /// Use this instead of [`Ui::add`] to paint overlays anchored to the knob.
+    ///
+    /// # Example
+    /// ```
+    /// # use egui_fancy_knob::{Knob, KnobStyle};
+    /// # let ctx = egui::Context::default();
+    /// # let _ = ctx.run(Default::default(), |ctx| {
+    /// # egui::CentralPanel::default().show(ctx, |ui| {
+    /// let mut value = 0.5;
+    /// let knob = Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper).show(ui);
+    /// if let Some(geometry) = knob.geometry {
+    ///     // Mark where modulation would take the value.
+    ///     let tip = geometry.point_at(geometry.angle_at(0.8), geometry.radius);
+    ///     ui.painter().circle_filled(tip, 2.0, egui::Color32::YELLOW);
+    /// }
+    /// # });
+    /// # });
+    /// ```
+    pub fn show(mut self, ui: &mut Ui) -> KnobResponse {
+        self.clamp_out_of_range();
+        let fine_step = self.apply_fine_step(ui);
+        if self
+            .fallback_width
+            .is_some_and(|width| ui.available_width() < width)
+        {
+            return KnobResponse::new(self.drag_value_ui(ui));
         }
+        self.animations = self.animations.effective(ui);
+        let placement = self.place(ui);
 
-        ui.add_space(vertical_margin);
+        let mut pass = self.begin(ui, &placement);
+        self.handle_pointer(ui, &mut pass, &placement, fine_step);
+        self.handle_controls(ui, &mut pass, &placement);
+        self.handle_keys(ui, &mut pass);
+        self.handle_external(ui, &mut pass);
+        self.handle_commands(ui, &mut pass);
+        self.describe(ui, &mut pass);
+        self.update_state(ui, &mut pass);
 
-        response
+        self.enabled |= pass.read_only;
+        if !self.enabled && self.dim_when_disabled {
+            self.dim();
+        }
+        // Knobs scrolled out of view still handle interaction above, but skip all painting.
+        let geometry = if ui.is_rect_visible(placement.outer_rect) {
+            let geometry = self.paint(ui, &mut pass, &placement);
+            self.value_entry_ui(ui, &mut pass, placement.knob_rect());
+            self.paint_magnifier(ui, &pass.state, placement.knob_rect(), pass.response.id);
+            geometry
+        } else {
+            let start_angle = self.start_turn();
+            KnobGeometry {
+                center: placement.knob_rect().center(),
+                radius: placement.layout.knob_size * 0.5,
+                min_angle: TAU * start_angle,
+                max_angle: TAU * (start_angle + self.motion),
+                angle: TAU * (self.normalised(self.value) * self.motion + start_angle),
+            }
+        };
+        pass.finish(ui.ctx(), geometry)
+    }
+}
+
+impl<F: FnMut(f32)> Widget for Knob<'_, F> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        self.show(ui).response
     }
 }
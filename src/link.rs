@@ -0,0 +1,142 @@
+use egui::{Context, Id, Modifiers};
+
+use crate::state;
+
+/// How the two knobs of a [`KnobLink`] relate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LinkMode {
+    /// The second knob's value is always the first's times the ratio
+    Ratio(f32),
+    /// The two values always add up to the total, e.g. complementary dry and wet levels
+    Sum(f32),
+}
+
+/// Two knobs locked together: changing one moves the other to keep their values in a ratio, or
+/// adding up to a total.
+///
+/// Knobs are identified by the id given to [`crate::Knob::with_id`], and both join the link
+/// with [`crate::Knob::with_link`]. The other knob moves on its next frame, reporting its new
+/// value through its setter, and clamped to its range. Holding the link's modifiers while
+/// changing a knob breaks the link for that change.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Knob, KnobLink, KnobStyle, LinkMode};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let (mut attack, mut release) = (0.2, 0.8);
+/// let link = KnobLink::new("attack", "release", LinkMode::Sum(1.0));
+/// ui.add(Knob::new(attack, |v| attack = v, 0.0..=1.0, KnobStyle::Wiper).with_id("attack").with_link(&link));
+/// ui.add(Knob::new(release, |v| release = v, 0.0..=1.0, KnobStyle::Wiper).with_id("release").with_link(&link));
+/// # });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct KnobLink {
+    first: Id,
+    second: Id,
+    mode: LinkMode,
+    modifiers: Modifiers,
+}
+
+impl KnobLink {
+    /// A link between the knobs with ids `first` and `second`.
+    pub fn new(first: impl Into<Id>, second: impl Into<Id>, mode: LinkMode) -> Self {
+        Self {
+            first: first.into(),
+            second: second.into(),
+            mode,
+            modifiers: Modifiers::COMMAND | Modifiers::SHIFT,
+        }
+    }
+
+    /// Sets the modifiers that break the link while held. The default is command and shift,
+    /// ctrl and shift outside macOS, as alt already drags finely and flips to neutral.
+    pub fn with_break_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    pub(crate) fn break_modifiers(&self) -> Modifiers {
+        self.modifiers
+    }
+
+    /// Moves the other knob after the knob with `changed` moved to `value`.
+    pub(crate) fn update(&self, ctx: &Context, changed: Id, value: f32) {
+        let (other, other_value) = if changed == self.first {
+            let other_value = match self.mode {
+                LinkMode::Ratio(ratio) => value * ratio,
+                LinkMode::Sum(total) => total - value,
+            };
+            (self.second, other_value)
+        } else if changed == self.second {
+            let other_value = match self.mode {
+                LinkMode::Ratio(ratio) if ratio != 0.0 => value / ratio,
+                LinkMode::Ratio(_) => return,
+                LinkMode::Sum(total) => total - value,
+            };
+            (self.first, other_value)
+        } else {
+            return;
+        };
+        state::queue_value(ctx, other, other_value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KnobHarness;
+    use crate::{Knob, KnobStyle};
+    use egui::{Response, Ui};
+
+    /// Shows the two linked knobs, returning the first.
+    fn show_pair(ui: &mut Ui, values: &mut [f32; 2], link: &KnobLink) -> Response {
+        let [first, second] = values;
+        let response = ui.add(
+            Knob::new(*first, |v| *first = v, 0.0..=1.0, KnobStyle::Wiper)
+                .with_id("first")
+                .with_link(link),
+        );
+        ui.add(
+            Knob::new(*second, |v| *second = v, 0.0..=2.0, KnobStyle::Wiper)
+                .with_id("second")
+                .with_link(link),
+        );
+        response
+    }
+
+    #[test]
+    fn sum_keeps_the_total() {
+        let link = KnobLink::new("first", "second", LinkMode::Sum(1.0));
+        let mut values = [0.2, 0.8];
+        let mut harness = KnobHarness::new();
+        harness.drag(-40.0, |ui| show_pair(ui, &mut values, &link));
+        harness.idle(2, |ui| show_pair(ui, &mut values, &link));
+        assert!(values[0] > 0.2);
+        assert!((values[0] + values[1] - 1.0).abs() < 1e-5, "{values:?}");
+    }
+
+    #[test]
+    fn ratio_follows_the_first_knob() {
+        let link = KnobLink::new("first", "second", LinkMode::Ratio(2.0));
+        let mut values = [0.2, 0.4];
+        let mut harness = KnobHarness::new();
+        harness.drag(-40.0, |ui| show_pair(ui, &mut values, &link));
+        harness.idle(2, |ui| show_pair(ui, &mut values, &link));
+        assert!(values[0] > 0.2);
+        assert!((values[1] - values[0] * 2.0).abs() < 1e-5, "{values:?}");
+    }
+
+    #[test]
+    fn break_modifiers_leave_the_other_knob() {
+        let link = KnobLink::new("first", "second", LinkMode::Sum(1.0));
+        let mut values = [0.2, 0.8];
+        let mut harness = KnobHarness::new().with_modifiers(Modifiers::COMMAND | Modifiers::SHIFT);
+        harness.drag(-40.0, |ui| show_pair(ui, &mut values, &link));
+        harness.idle(2, |ui| show_pair(ui, &mut values, &link));
+        assert!(values[0] > 0.2);
+        assert_eq!(values[1], 0.8);
+    }
+}
@@ -0,0 +1,222 @@
+use crate::{Knob, KnobStyle};
+use egui::{Color32, Response, Stroke, Ui, Widget};
+
+/// The knob a [`MacroKnob`] shows, as passed to [`MacroKnob::with_knob`].
+pub type MacroKnobInner<'k> = Knob<'k, &'k mut dyn FnMut(f32)>;
+
+type Configure<'a> = Box<dyn for<'k> FnOnce(MacroKnobInner<'k>) -> MacroKnobInner<'k> + 'a>;
+type Curve<'a> = Box<dyn Fn(f32) -> f32 + 'a>;
+
+/// One parameter a [`MacroKnob`] controls.
+pub struct MacroDestination<'a> {
+    amount: f32,
+    curve: Option<Curve<'a>>,
+    color: Color32,
+    set_value: Box<dyn FnMut(f32) + 'a>,
+}
+
+impl<'a> MacroDestination<'a> {
+    /// A destination that the macro moves by up to `amount`, in the destination's own units.
+    ///
+    /// Whenever the macro changes, `set_value` gets the macro's position times `amount`: add it
+    /// to the destination's base value, like a modulation amount. Negative amounts move the
+    /// destination down as the macro goes up.
+    pub fn new(amount: f32, set_value: impl FnMut(f32) + 'a) -> Self {
+        Self {
+            amount,
+            curve: None,
+            color: Color32::LIGHT_BLUE,
+            set_value: Box::new(set_value),
+        }
+    }
+
+    /// Shapes the macro's position before it's scaled by the amount. `curve` maps `0.0..=1.0`
+    /// to `0.0..=1.0`; the default is linear.
+    pub fn with_curve(mut self, curve: impl Fn(f32) -> f32 + 'a) -> Self {
+        self.curve = Some(Box::new(curve));
+        self
+    }
+
+    /// Sets the color of the destination's amount indicator.
+    pub fn with_color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// The macro's `position`, through the curve.
+    fn shaped(&self, position: f32) -> f32 {
+        self.curve
+            .as_ref()
+            .map_or(position, |curve| curve(position))
+            .clamp(0.0, 1.0)
+    }
+}
+
+/// A macro knob that moves several destination parameters at once, each by its own amount and
+/// curve, as in the macro controls of synth hosts.
+///
+/// Each destination gets a thin arc around the knob showing how far the macro currently moves
+/// it, in the destination's color.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{KnobStyle, MacroDestination, MacroKnob};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut brightness = 0.0;
+/// let (mut cutoff_mod, mut resonance_mod) = (0.0, 0.0);
+/// ui.add(
+///     MacroKnob::new(brightness, |v| brightness = v, KnobStyle::Wiper)
+///         .with_destination(MacroDestination::new(4000.0, |hz| cutoff_mod = hz))
+///         .with_destination(
+///             MacroDestination::new(-0.3, |q| resonance_mod = q).with_curve(|t| t * t),
+///         ),
+/// );
+/// # });
+/// # });
+/// ```
+pub struct MacroKnob<'a> {
+    value: f32,
+    set_value: Box<dyn FnMut(f32) + 'a>,
+    style: KnobStyle,
+    destinations: Vec<MacroDestination<'a>>,
+    configure: Option<Configure<'a>>,
+}
+
+impl<'a> MacroKnob<'a> {
+    /// A macro at `value`, in `0.0..=1.0`.
+    pub fn new(value: f32, set_value: impl FnMut(f32) + 'a, style: KnobStyle) -> Self {
+        Self {
+            value,
+            set_value: Box::new(set_value),
+            style,
+            destinations: Vec::new(),
+            configure: None,
+        }
+    }
+
+    /// Adds a parameter the macro controls.
+    pub fn with_destination(mut self, destination: MacroDestination<'a>) -> Self {
+        self.destinations.push(destination);
+        self
+    }
+
+    /// Customizes the underlying knob, e.g. its size, colors and label.
+    ///
+    /// The range and label format are already set.
+    pub fn with_knob(
+        mut self,
+        configure: impl for<'k> FnOnce(MacroKnobInner<'k>) -> MacroKnobInner<'k> + 'a,
+    ) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+impl Widget for MacroKnob<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let mut new_value = None;
+        let mut set_value = |value: f32| new_value = Some(value);
+
+        let mut knob = Knob::new(
+            self.value,
+            &mut set_value as &mut dyn FnMut(f32),
+            0.0..=1.0,
+            self.style,
+        )
+        .with_label_format(|value| format!("{:.0}%", value * 100.0));
+        if let Some(configure) = self.configure {
+            knob = configure(knob);
+        }
+        let knob_response = knob.show(ui);
+
+        let value = new_value.unwrap_or(self.value);
+        if let Some(value) = new_value {
+            (self.set_value)(value);
+            for destination in &mut self.destinations {
+                let offset = destination.amount * destination.shaped(value);
+                (destination.set_value)(offset);
+            }
+        }
+
+        if let Some(geometry) = knob_response.geometry
+            && ui.is_rect_visible(knob_response.rect)
+        {
+            let width = (geometry.radius * 0.06).max(1.0);
+            for (i, destination) in self.destinations.iter().enumerate() {
+                let radius = geometry.radius + width * (2.5 + 2.0 * i as f32);
+                let reach = destination.shaped(value);
+                // Negative amounts are drawn back from the end of the range.
+                let (from, to) = if destination.amount < 0.0 {
+                    (1.0 - reach, 1.0)
+                } else {
+                    (0.0, reach)
+                };
+                let points: Vec<_> = (0..=24)
+                    .map(|step| {
+                        let position = egui::lerp(from..=to, step as f32 / 24.0);
+                        geometry.point_at(geometry.angle_at(position), radius)
+                    })
+                    .collect();
+                ui.painter().add(egui::Shape::line(
+                    points,
+                    Stroke::new(width, destination.color),
+                ));
+            }
+        }
+
+        knob_response.response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KnobHarness;
+
+    /// The macro's value and the offsets its two destinations were last given.
+    #[derive(Default)]
+    struct Macro {
+        value: f32,
+        cutoff: f32,
+        resonance: f32,
+    }
+
+    fn show_macro(ui: &mut Ui, state: &mut Macro) -> Response {
+        let Macro {
+            value,
+            cutoff,
+            resonance,
+        } = state;
+        ui.add(
+            MacroKnob::new(*value, |v| *value = v, KnobStyle::Wiper)
+                .with_destination(MacroDestination::new(4000.0, |hz| *cutoff = hz))
+                .with_destination(
+                    MacroDestination::new(-0.5, |q| *resonance = q).with_curve(|t| t * t),
+                ),
+        )
+    }
+
+    #[test]
+    fn destinations_follow_the_macro() {
+        let mut state = Macro::default();
+        let mut harness = KnobHarness::new();
+        harness.drag(-40.0, |ui| show_macro(ui, &mut state));
+        assert!(state.value > 0.0);
+        assert!((state.cutoff - 4000.0 * state.value).abs() < 1e-2);
+        assert!((state.resonance + 0.5 * state.value * state.value).abs() < 1e-6);
+    }
+
+    #[test]
+    fn destinations_are_left_alone_until_the_macro_moves() {
+        let mut state = Macro {
+            value: 0.5,
+            cutoff: 123.0,
+            resonance: 0.25,
+        };
+        let mut harness = KnobHarness::new();
+        harness.idle(3, |ui| show_macro(ui, &mut state));
+        assert_eq!((state.cutoff, state.resonance), (123.0, 0.25));
+    }
+}
@@ -0,0 +1,146 @@
+use crate::normalise::{
+    KnobSpec, normalised_from_value, normalised_step, snap_normalised, value_from_normalised,
+};
+use std::ops::RangeInclusive;
+
+/// The conversion between a knob's values and its normalised `0.0..=1.0` positions, without a
+/// [`egui::Ui`].
+///
+/// This is the same mapping [`crate::Knob`] uses, so headless hosts like audio engines and CLI
+/// tools can stay in step with the GUI. The builder methods mirror the knob's.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::KnobMapping;
+/// let cutoff = KnobMapping::new(20.0..=20_000.0).logarithmic(true);
+/// let middle = cutoff.value(0.5);
+/// assert!((middle - 632.46).abs() < 0.01);
+/// assert!((cutoff.normalised(middle) - 0.5).abs() < 1e-6);
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobMapping {
+    range: RangeInclusive<f32>,
+    spec: KnobSpec,
+    step: Option<f32>,
+}
+
+impl KnobMapping {
+    /// A linear mapping over `range`.
+    pub fn new(range: RangeInclusive<f32>) -> Self {
+        Self {
+            range,
+            spec: KnobSpec::default(),
+            step: None,
+        }
+    }
+
+    /// See [`crate::Knob::logarithmic`].
+    pub fn logarithmic(mut self, logarithmic: bool) -> Self {
+        self.spec.logarithmic = logarithmic;
+        self
+    }
+
+    /// See [`crate::Knob::smallest_finite`].
+    pub fn smallest_finite(mut self, smallest_finite: f32) -> Self {
+        self.spec.smallest_finite = smallest_finite.abs();
+        self
+    }
+
+    /// See [`crate::Knob::largest_finite`].
+    pub fn largest_finite(mut self, largest_finite: f32) -> Self {
+        self.spec.largest_finite = largest_finite.abs();
+        self
+    }
+
+    /// See [`crate::Knob::with_step`].
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn range(&self) -> RangeInclusive<f32> {
+        self.range.clone()
+    }
+
+    /// The normalised position of `value`, clamped to `0.0..=1.0`.
+    pub fn normalised(&self, value: f32) -> f32 {
+        normalised_from_value(value, self.range.clone(), &self.spec)
+    }
+
+    /// The value at normalised position `normalised`, clamped to the range.
+    pub fn value(&self, normalised: f32) -> f32 {
+        value_from_normalised(normalised, self.range.clone(), &self.spec)
+    }
+
+    /// The step as a fraction of the whole range, if there is one.
+    pub fn normalised_step(&self) -> Option<f32> {
+        self.step
+            .map(|step| normalised_step(step, self.range.clone()))
+    }
+
+    /// Rounds a normalised position to the nearest step, as dragging does.
+    pub fn snap(&self, normalised: f32) -> f32 {
+        match self.normalised_step() {
+            Some(step) => snap_normalised(normalised, step),
+            None => normalised.clamp(0.0, 1.0),
+        }
+    }
+
+    /// The value a drag would land on at normalised position `normalised`.
+    pub fn snapped_value(&self, normalised: f32) -> f32 {
+        self.value(self.snap(normalised))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn linear_mapping() {
+        let mapping = KnobMapping::new(-10.0..=10.0);
+        assert_eq!(mapping.value(0.0), -10.0);
+        assert_eq!(mapping.value(0.5), 0.0);
+        assert_eq!(mapping.value(1.0), 10.0);
+        assert_eq!(mapping.normalised(5.0), 0.75);
+    }
+
+    #[test]
+    fn clamps_to_the_range() {
+        let mapping = KnobMapping::new(0.0..=1.0);
+        assert_eq!(mapping.normalised(2.0), 1.0);
+        assert_eq!(mapping.normalised(-1.0), 0.0);
+        assert_eq!(mapping.value(1.5), 1.0);
+    }
+
+    #[test]
+    fn reversed_range() {
+        let mapping = KnobMapping::new(10.0..=0.0);
+        assert_eq!(mapping.value(0.0), 10.0);
+        assert_eq!(mapping.value(1.0), 0.0);
+        assert_eq!(mapping.normalised(2.5), 0.75);
+    }
+
+    #[test]
+    fn logarithmic_mapping() {
+        let mapping = KnobMapping::new(1.0..=100.0).logarithmic(true);
+        assert!((mapping.value(0.5) - 10.0).abs() < 1e-4);
+        assert!((mapping.normalised(10.0) - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn snapping_follows_the_step() {
+        let mapping = KnobMapping::new(0.0..=10.0).with_step(2.5);
+        assert_eq!(mapping.normalised_step(), Some(0.25));
+        assert_eq!(mapping.snap(0.3), 0.25);
+        assert_eq!(mapping.snapped_value(0.4), 5.0);
+    }
+
+    #[test]
+    fn snapping_without_a_step_only_clamps() {
+        let mapping = KnobMapping::new(0.0..=10.0);
+        assert_eq!(mapping.normalised_step(), None);
+        assert_eq!(mapping.snap(0.3), 0.3);
+        assert_eq!(mapping.snap(1.3), 1.0);
+    }
+}
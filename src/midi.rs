@@ -0,0 +1,122 @@
+use crate::state;
+use egui::{Context, Id};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// A MIDI control change source: a controller number on a channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MidiCc {
+    /// MIDI channel, `0..=15`
+    pub channel: u8,
+    /// Controller number, `0..=127`
+    pub controller: u8,
+}
+
+/// Routes incoming MIDI CC messages to knobs, and learns new bindings.
+///
+/// Knobs are identified by the id given to [`crate::Knob::with_id`]. Incoming values are
+/// applied in the knob's normalised space, so logarithmic knobs follow their taper, and are
+/// reported through the knob's setter on its next frame.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Knob, KnobMidiMap, KnobStyle, MidiCc};
+/// # let ctx = egui::Context::default();
+/// let mut midi = KnobMidiMap::default();
+/// let cutoff = egui::Id::new("cutoff");
+///
+/// // When the knob is clicked in learn mode, it is armed...
+/// midi.set_armed(cutoff, true);
+/// // ...and the next CC to arrive is bound to it.
+/// midi.handle_cc(&ctx, MidiCc { channel: 0, controller: 74 }, 64);
+/// assert_eq!(midi.binding(cutoff), Some(MidiCc { channel: 0, controller: 74 }));
+///
+/// let mut value = 0.0;
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// # let learning = false;
+/// let armed = midi.is_armed(cutoff);
+/// let mut knob = Knob::new(value, |v| value = v, 0.0..=127.0, KnobStyle::Wiper).with_id(cutoff);
+/// if learning {
+///     knob = knob.with_learn(armed, |armed| midi.set_armed(cutoff, armed));
+/// }
+/// ui.add(knob);
+/// # });
+/// # });
+/// assert_eq!(value, 64.0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct KnobMidiMap {
+    bindings: HashMap<MidiCc, Id>,
+    armed: Option<Id>,
+}
+
+impl KnobMidiMap {
+    /// Converts a 7-bit CC value to the knob's normalised `0.0..=1.0` range.
+    pub fn normalised_from_cc(value: u8) -> f32 {
+        value.min(127) as f32 / 127.0
+    }
+
+    /// Routes `cc` to the knob with `knob_id`, replacing any other binding of either.
+    pub fn bind(&mut self, cc: MidiCc, knob_id: Id) {
+        self.unbind(knob_id);
+        self.bindings.insert(cc, knob_id);
+    }
+
+    /// Removes the binding of the knob with `knob_id`, if any.
+    pub fn unbind(&mut self, knob_id: Id) {
+        self.bindings.retain(|_, id| *id != knob_id);
+    }
+
+    /// The CC bound to the knob with `knob_id`, if any.
+    pub fn binding(&self, knob_id: Id) -> Option<MidiCc> {
+        self.bindings
+            .iter()
+            .find_map(|(cc, id)| (*id == knob_id).then_some(*cc))
+    }
+
+    /// The knob that `cc` is routed to, if any.
+    pub fn knob_for(&self, cc: MidiCc) -> Option<Id> {
+        self.bindings.get(&cc).copied()
+    }
+
+    /// Arms or disarms the knob with `knob_id` for learning. Only one knob is armed at a time.
+    ///
+    /// This fits the `on_arm` callback of [`crate::Knob::with_learn`].
+    pub fn set_armed(&mut self, knob_id: Id, armed: bool) {
+        if armed {
+            self.armed = Some(knob_id);
+        } else if self.armed == Some(knob_id) {
+            self.armed = None;
+        }
+    }
+
+    /// Whether the knob with `knob_id` is armed for learning.
+    pub fn is_armed(&self, knob_id: Id) -> bool {
+        self.armed == Some(knob_id)
+    }
+
+    /// The knob armed for learning, if any.
+    pub fn armed(&self) -> Option<Id> {
+        self.armed
+    }
+
+    /// Feeds an incoming CC message with a 7-bit `value`.
+    ///
+    /// If a knob is armed, `cc` is bound to it and it is disarmed first. Returns the knob the
+    /// value was sent to, if any. Can be called from a MIDI thread, as long as the map itself is
+    /// shared safely.
+    pub fn handle_cc(&mut self, ctx: &Context, cc: MidiCc, value: u8) -> Option<Id> {
+        if let Some(armed) = self.armed.take() {
+            self.bind(cc, armed);
+        }
+        let knob_id = self.knob_for(cc)?;
+        state::queue_normalised(
+            ctx,
+            knob_id,
+            Self::normalised_from_cc(value),
+            Duration::ZERO,
+        );
+        Some(knob_id)
+    }
+}
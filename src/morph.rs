@@ -0,0 +1,105 @@
+use crate::{Knob, KnobStyle};
+use egui::{Response, Ui, Widget};
+
+/// The knob a [`MorphKnob`] shows, as passed to [`MorphKnob::with_knob`].
+pub type MorphKnobInner<'k> = Knob<'k, &'k mut dyn FnMut(f32)>;
+
+type Configure<'a> = Box<dyn for<'k> FnOnce(MorphKnobInner<'k>) -> MorphKnobInner<'k> + 'a>;
+type OnMorph<'a> = Box<dyn FnMut(f32, &[f32]) + 'a>;
+
+/// A macro knob that morphs a set of values between two snapshots, A at 0% and B at 100%.
+///
+/// Whenever the knob is moved, `on_morph` gets the new morph position and every value
+/// interpolated between the snapshots. If they have different lengths, the extra values are
+/// left out.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{KnobStyle, LabelPosition, MorphKnob};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let a = [0.2, 0.8, 0.5];
+/// let b = [0.9, 0.1, 0.5];
+/// let mut morph = 0.0;
+/// let mut patch = a;
+/// ui.add(
+///     MorphKnob::new(morph, &a, &b, KnobStyle::Wiper, |position, values| {
+///         morph = position;
+///         patch.copy_from_slice(values);
+///     })
+///     .with_knob(|knob| knob.with_label("Morph", LabelPosition::Bottom)),
+/// );
+/// # });
+/// # });
+/// ```
+pub struct MorphKnob<'a> {
+    morph: f32,
+    a: &'a [f32],
+    b: &'a [f32],
+    style: KnobStyle,
+    on_morph: OnMorph<'a>,
+    configure: Option<Configure<'a>>,
+}
+
+impl<'a> MorphKnob<'a> {
+    /// A knob at `morph`, in `0.0..=1.0`, between snapshots `a` and `b`.
+    pub fn new(
+        morph: f32,
+        a: &'a [f32],
+        b: &'a [f32],
+        style: KnobStyle,
+        on_morph: impl FnMut(f32, &[f32]) + 'a,
+    ) -> Self {
+        Self {
+            morph,
+            a,
+            b,
+            style,
+            on_morph: Box::new(on_morph),
+            configure: None,
+        }
+    }
+
+    /// Customizes the underlying knob, e.g. its size, colors and label.
+    ///
+    /// The range and label format are already set.
+    pub fn with_knob(
+        mut self,
+        configure: impl for<'k> FnOnce(MorphKnobInner<'k>) -> MorphKnobInner<'k> + 'a,
+    ) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+impl Widget for MorphKnob<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let mut new_morph = None;
+        let mut set_morph = |morph: f32| new_morph = Some(morph);
+
+        let mut knob = Knob::new(
+            self.morph,
+            &mut set_morph as &mut dyn FnMut(f32),
+            0.0..=1.0,
+            self.style,
+        )
+        .with_label_format(|morph| format!("{:.0}%", morph * 100.0));
+        if let Some(configure) = self.configure {
+            knob = configure(knob);
+        }
+        let response = ui.add(knob);
+
+        if let Some(morph) = new_morph {
+            let values: Vec<f32> = self
+                .a
+                .iter()
+                .zip(self.b)
+                .map(|(&a, &b)| egui::lerp(a..=b, morph))
+                .collect();
+            (self.on_morph)(morph, &values);
+        }
+
+        response
+    }
+}
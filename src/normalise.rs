@@ -1,7 +1,31 @@
-use crate::{INFINITY, KnobSpec};
-use egui::{lerp, remap, remap_clamp};
+//! Nothing in here depends on egui, so headless hosts get exactly the same mapping through
+//! [`crate::KnobMapping`].
+
 use std::ops::RangeInclusive;
 
+const INFINITY: f64 = f64::INFINITY;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobSpec {
+    pub logarithmic: bool,
+    /// For logarithmic knobs, the smallest positive value we are interested in before the knob
+    /// switches to `0.0`.
+    pub smallest_finite: f32,
+    /// For logarithmic knobs, the largest positive value we are interested in before the knob
+    /// switches to `INFINITY`.
+    pub largest_finite: f32,
+}
+
+impl Default for KnobSpec {
+    fn default() -> Self {
+        Self {
+            logarithmic: false,
+            smallest_finite: 1e-6,
+            largest_finite: 1e6,
+        }
+    }
+}
+
 // ----------------------------------------------------------------------------
 
 // Helpers for converting knob range to/from normalized [0-1] range.
@@ -19,16 +43,16 @@ use std::ops::RangeInclusive;
 /// When the user asks for an infinitely large range (e.g. logarithmic from zero),
 ///
 /// give a scale that this many orders of magnitude in size.
-const INF_RANGE_MAGNITUDE: f32 = 10.0;
+const INF_RANGE_MAGNITUDE: f64 = 10.0;
 
-pub fn value_from_normalised(normalised: f32, range: RangeInclusive<f32>, spec: &KnobSpec) -> f32 {
+fn value_from_normalised_f64(normalised: f64, range: RangeInclusive<f64>, spec: &KnobSpec) -> f64 {
     let (min, max) = (*range.start(), *range.end());
     if min.is_nan() || max.is_nan() {
-        f32::NAN
+        f64::NAN
     } else if min == max {
         min
     } else if min > max {
-        value_from_normalised(1.0 - normalised, max..=min, spec)
+        value_from_normalised_f64(1.0 - normalised, max..=min, spec)
     } else if normalised <= 0.0 {
         min
     } else if normalised >= 1.0 {
@@ -36,24 +60,24 @@ pub fn value_from_normalised(normalised: f32, range: RangeInclusive<f32>, spec:
     } else if spec.logarithmic {
         if max <= 0.0 {
             // non-positive range
-            -value_from_normalised(normalised, -min..=-max, spec)
+            -value_from_normalised_f64(normalised, -min..=-max, spec)
         } else if 0.0 <= min {
             let (min_log, max_log) = range_log10(min, max, spec);
             let log = lerp(min_log..=max_log, normalised);
-            10f32.powf(log)
+            10f64.powf(log)
         } else {
             assert!(min < 0.0 && 0.0 < max);
             let zero_cutoff = logarithmic_zero_cutoff(min, max);
             if normalised < zero_cutoff {
                 // negative
-                value_from_normalised(
+                value_from_normalised_f64(
                     remap(normalised, 0.0..=zero_cutoff, 0.0..=1.0),
                     min..=0.0,
                     spec,
                 )
             } else {
                 // positive
-                value_from_normalised(
+                value_from_normalised_f64(
                     remap(normalised, zero_cutoff..=1.0, 0.0..=1.0),
                     0.0..=max,
                     spec,
@@ -69,15 +93,15 @@ pub fn value_from_normalised(normalised: f32, range: RangeInclusive<f32>, spec:
     }
 }
 
-pub fn normalised_from_value(value: f32, range: RangeInclusive<f32>, spec: &KnobSpec) -> f32 {
+fn normalised_from_value_f64(value: f64, range: RangeInclusive<f64>, spec: &KnobSpec) -> f64 {
     let (min, max) = (*range.start(), *range.end());
 
     if min.is_nan() || max.is_nan() {
-        f32::NAN
+        f64::NAN
     } else if min == max {
         0.5 // empty range, show centre of slider. 
     } else if min > max {
-        1.0 - normalised_from_value(value, max..=min, spec)
+        1.0 - normalised_from_value_f64(value, max..=min, spec)
     } else if value <= min {
         0.0
     } else if value >= max {
@@ -85,7 +109,7 @@ pub fn normalised_from_value(value: f32, range: RangeInclusive<f32>, spec: &Knob
     } else if spec.logarithmic {
         if max <= 0.0 {
             // non-positive range
-            normalised_from_value(-value, -min..=-max, spec)
+            normalised_from_value_f64(-value, -min..=-max, spec)
         } else if 0.0 <= min {
             let (min_log, max_log) = range_log10(min, max, spec);
             let value_log = value.log10();
@@ -96,14 +120,14 @@ pub fn normalised_from_value(value: f32, range: RangeInclusive<f32>, spec: &Knob
             if value < 0.0 {
                 // negative
                 remap(
-                    normalised_from_value(value, min..=0.0, spec),
+                    normalised_from_value_f64(value, min..=0.0, spec),
                     0.0..=1.0,
                     0.0..=zero_cutoff,
                 )
             } else {
                 // positive
                 remap(
-                    normalised_from_value(value, 0.0..=max, spec),
+                    normalised_from_value_f64(value, 0.0..=max, spec),
                     0.0..=1.0,
                     zero_cutoff..=1.0,
                 )
@@ -118,21 +142,24 @@ pub fn normalised_from_value(value: f32, range: RangeInclusive<f32>, spec: &Knob
     }
 }
 
-fn range_log10(min: f32, max: f32, spec: &KnobSpec) -> (f32, f32) {
+fn range_log10(min: f64, max: f64, spec: &KnobSpec) -> (f64, f64) {
     assert!(spec.logarithmic);
     assert!(min <= max);
 
     if min == 0.0 && max == INFINITY {
-        (spec.smallest_finite.log10(), spec.largest_finite.log10())
+        (
+            (spec.smallest_finite as f64).log10(),
+            (spec.largest_finite as f64).log10(),
+        )
     } else if min == 0.0 {
-        if spec.smallest_finite < max {
-            (spec.smallest_finite.log10(), max.log10())
+        if (spec.smallest_finite as f64) < max {
+            ((spec.smallest_finite as f64).log10(), max.log10())
         } else {
             (max.log10() - INF_RANGE_MAGNITUDE, max.log10())
         }
-    } else if max == f32::INFINITY {
-        if min < spec.largest_finite {
-            (min.log10(), spec.largest_finite.log10())
+    } else if max == f64::INFINITY {
+        if min < spec.largest_finite as f64 {
+            (min.log10(), (spec.largest_finite as f64).log10())
         } else {
             (min.log10(), min.log10() + INF_RANGE_MAGNITUDE)
         }
@@ -141,7 +168,7 @@ fn range_log10(min: f32, max: f32, spec: &KnobSpec) -> (f32, f32) {
     }
 }
 
-fn logarithmic_zero_cutoff(min: f32, max: f32) -> f32 {
+fn logarithmic_zero_cutoff(min: f64, max: f64) -> f64 {
     assert!(min < 0.0 && 0.0 < max);
 
     let min_magnitude = if min == -INFINITY {
@@ -162,3 +189,135 @@ fn logarithmic_zero_cutoff(min: f32, max: f32) -> f32 {
     );
     cutoff
 }
+
+/// The value at `normalised`. The math is done in `f64`, so wide and logarithmic ranges don't
+/// visibly quantise.
+pub fn value_from_normalised(normalised: f32, range: RangeInclusive<f32>, spec: &KnobSpec) -> f32 {
+    value_from_normalised_f64(normalised as f64, widen(range), spec) as f32
+}
+
+/// The normalised position of `value`, computed in `f64` like [`value_from_normalised`].
+pub fn normalised_from_value(value: f32, range: RangeInclusive<f32>, spec: &KnobSpec) -> f32 {
+    normalised_from_value_f64(value as f64, widen(range), spec) as f32
+}
+
+fn widen(range: RangeInclusive<f32>) -> RangeInclusive<f64> {
+    *range.start() as f64..=*range.end() as f64
+}
+
+/// `step`, in value units, as a fraction of the whole range.
+pub fn normalised_step(step: f32, range: RangeInclusive<f32>) -> f32 {
+    step / (range.end() - range.start()).abs()
+}
+
+/// Rounds a normalised position to the nearest multiple of the normalised `step`.
+pub fn snap_normalised(normalised: f32, step: f32) -> f32 {
+    if step > 0.0 {
+        ((normalised / step).round() * step).clamp(0.0, 1.0)
+    } else {
+        normalised
+    }
+}
+
+/// How far apart, in normalised units, two positions can be and still count as the same, so
+/// float error from the value mapping round trip isn't mistaken for a different value.
+pub const POSITION_TOLERANCE: f32 = 1e-4;
+
+/// Whether normalised positions `a` and `b` are within [`POSITION_TOLERANCE`].
+pub fn same_position(a: f32, b: f32) -> bool {
+    (a - b).abs() <= POSITION_TOLERANCE
+}
+
+fn lerp(range: RangeInclusive<f64>, t: f64) -> f64 {
+    (1.0 - t) * range.start() + t * range.end()
+}
+
+fn remap(x: f64, from: RangeInclusive<f64>, to: RangeInclusive<f64>) -> f64 {
+    let t = (x - from.start()) / (from.end() - from.start());
+    lerp(to, t)
+}
+
+fn remap_clamp(x: f64, from: RangeInclusive<f64>, to: RangeInclusive<f64>) -> f64 {
+    if from.end() < from.start() {
+        return remap_clamp(x, *from.end()..=*from.start(), *to.end()..=*to.start());
+    }
+    if x <= *from.start() {
+        *to.start()
+    } else if *from.end() <= x {
+        *to.end()
+    } else {
+        remap(x, from, to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalised_step_is_a_fraction_of_the_range() {
+        assert_eq!(normalised_step(10.0, 0.0..=100.0), 0.1);
+        assert_eq!(normalised_step(10.0, 100.0..=0.0), 0.1);
+        assert_eq!(normalised_step(0.5, -1.0..=1.0), 0.25);
+    }
+
+    #[test]
+    fn snap_rounds_to_the_nearest_step() {
+        assert_eq!(snap_normalised(0.37, 0.25), 0.25);
+        assert_eq!(snap_normalised(0.38, 0.25), 0.5);
+        assert_eq!(snap_normalised(0.0, 0.25), 0.0);
+        assert_eq!(snap_normalised(1.0, 0.25), 1.0);
+    }
+
+    #[test]
+    fn snap_stays_in_range() {
+        // The last step can overshoot the end when the step doesn't divide the range.
+        assert!((snap_normalised(0.99, 0.3) - 0.9).abs() < 1e-6);
+        assert_eq!(snap_normalised(1.2, 0.3), 1.0);
+        assert_eq!(snap_normalised(-0.2, 0.3), 0.0);
+    }
+
+    #[test]
+    fn snap_without_a_step_leaves_the_position() {
+        assert_eq!(snap_normalised(0.37, 0.0), 0.37);
+    }
+
+    #[test]
+    fn round_trips_stay_put() {
+        let spec = KnobSpec::default();
+        for range in [0.0..=1.0, -1e6..=1e6, 20.0..=20_000.0] {
+            for i in 0..=100 {
+                let value = value_from_normalised(i as f32 / 100.0, range.clone(), &spec);
+                let mut back = value;
+                // Repeated conversions, like a knob's every frame, mustn't drift.
+                for _ in 0..1000 {
+                    back = value_from_normalised(
+                        normalised_from_value(back, range.clone(), &spec),
+                        range.clone(),
+                        &spec,
+                    );
+                }
+                assert_eq!(back, value, "drifted in {range:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn logarithmic_round_trips_stay_put() {
+        let spec = KnobSpec {
+            logarithmic: true,
+            ..Default::default()
+        };
+        for range in [20.0..=20_000.0, 0.0..=1e6, -100.0..=100.0] {
+            for i in 0..=100 {
+                let normalised = i as f32 / 100.0;
+                let value = value_from_normalised(normalised, range.clone(), &spec);
+                let back = normalised_from_value(value, range.clone(), &spec);
+                assert!(
+                    (back - normalised).abs() < 1e-5,
+                    "{normalised} went to {value} and back to {back} in {range:?}"
+                );
+            }
+        }
+    }
+}
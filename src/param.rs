@@ -0,0 +1,199 @@
+use crate::{Knob, KnobStyle};
+use egui::{Response, Ui, Widget};
+
+/// The knob a [`ParamKnob`] shows, as passed to [`ParamKnob::with_knob`].
+pub type ParamKnobInner<'k> = Knob<'k, &'k mut dyn FnMut(f32)>;
+
+type Configure<'a> = Box<dyn for<'k> FnOnce(ParamKnobInner<'k>) -> ParamKnobInner<'k> + 'a>;
+
+/// A host-automatable plugin parameter, as seen by a [`ParamKnob`].
+///
+/// Everything is in normalised `0.0..=1.0` space, so the parameter's own mapping (skew, steps,
+/// etc.) decides how the knob moves.
+///
+/// There is no built-in nih-plug adapter, because nih-plug is only published as a git
+/// repository and crates.io doesn't allow git dependencies. The adapter is a few lines in the
+/// plugin instead, on a pair of the `Param` and the editor's `ParamSetter`:
+///
+/// ```
+/// # use egui_fancy_knob::KnobParam;
+/// # // Stand-ins with the signatures of nih-plug's `Param` and `ParamSetter`.
+/// # trait Param {
+/// #     fn unmodulated_normalized_value(&self) -> f32;
+/// #     fn default_normalized_value(&self) -> f32;
+/// #     fn step_count(&self) -> Option<usize>;
+/// #     fn normalized_value_to_string(&self, normalized: f32, include_unit: bool) -> String;
+/// # }
+/// # struct ParamSetter<'a>(std::marker::PhantomData<&'a ()>);
+/// # impl ParamSetter<'_> {
+/// #     fn begin_set_parameter<P: Param>(&self, _param: &P) {}
+/// #     fn set_parameter_normalized<P: Param>(&self, _param: &P, _normalized: f32) {}
+/// #     fn end_set_parameter<P: Param>(&self, _param: &P) {}
+/// # }
+/// struct NihParam<'a, P: Param> {
+///     param: &'a P,
+///     setter: &'a ParamSetter<'a>,
+/// }
+///
+/// impl<P: Param> KnobParam for NihParam<'_, P> {
+///     fn normalised(&self) -> f32 {
+///         self.param.unmodulated_normalized_value()
+///     }
+///     fn default_normalised(&self) -> f32 {
+///         self.param.default_normalized_value()
+///     }
+///     fn step_count(&self) -> Option<usize> {
+///         self.param.step_count()
+///     }
+///     fn display(&self, normalised: f32) -> String {
+///         self.param.normalized_value_to_string(normalised, true)
+///     }
+///     fn begin_gesture(&self) {
+///         self.setter.begin_set_parameter(self.param);
+///     }
+///     fn set_normalised(&self, normalised: f32) {
+///         self.setter.set_parameter_normalized(self.param, normalised);
+///     }
+///     fn end_gesture(&self) {
+///         self.setter.end_set_parameter(self.param);
+///     }
+/// }
+/// ```
+pub trait KnobParam {
+    /// The current normalised value, without modulation.
+    fn normalised(&self) -> f32;
+
+    /// The normalised value that double clicking resets to.
+    fn default_normalised(&self) -> f32;
+
+    /// For stepped parameters, the number of steps between the lowest and highest value.
+    fn step_count(&self) -> Option<usize> {
+        None
+    }
+
+    /// Text for a normalised value, including the unit.
+    fn display(&self, normalised: f32) -> String;
+
+    /// Called before the first change of a gesture, e.g. when a drag starts.
+    fn begin_gesture(&self);
+
+    /// Changes the value. Always called between [`KnobParam::begin_gesture`] and
+    /// [`KnobParam::end_gesture`].
+    fn set_normalised(&self, normalised: f32);
+
+    /// Called after the last change of a gesture, e.g. when a drag stops.
+    fn end_gesture(&self);
+}
+
+/// A knob bound to a [`KnobParam`], which brackets every change in a gesture so the host can
+/// record automation.
+///
+/// Drags are reported as one gesture from press to release. Changes made in a single frame,
+/// like a double click reset or an assistive technology action, get a gesture of their own.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{KnobParam, KnobStyle, LabelPosition, ParamKnob};
+/// # use std::cell::Cell;
+/// struct Gain(Cell<f32>);
+///
+/// impl KnobParam for Gain {
+///     fn normalised(&self) -> f32 {
+///         self.0.get()
+///     }
+///     fn default_normalised(&self) -> f32 {
+///         0.5
+///     }
+///     fn display(&self, normalised: f32) -> String {
+///         format!("{:.1} dB", normalised * 48.0 - 24.0)
+///     }
+///     fn begin_gesture(&self) {}
+///     fn set_normalised(&self, normalised: f32) {
+///         self.0.set(normalised);
+///     }
+///     fn end_gesture(&self) {}
+/// }
+///
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let gain = Gain(Cell::new(0.5));
+/// ui.add(
+///     ParamKnob::new(&gain, KnobStyle::Wiper)
+///         .with_knob(|knob| knob.with_size(50.0).with_label("Gain", LabelPosition::Bottom)),
+/// );
+/// # });
+/// # });
+/// ```
+pub struct ParamKnob<'a, P: KnobParam> {
+    param: &'a P,
+    style: KnobStyle,
+    configure: Option<Configure<'a>>,
+}
+
+impl<'a, P: KnobParam> ParamKnob<'a, P> {
+    pub fn new(param: &'a P, style: KnobStyle) -> Self {
+        Self {
+            param,
+            style,
+            configure: None,
+        }
+    }
+
+    /// Customizes the underlying knob, e.g. its size, colors and label.
+    ///
+    /// The range, step, neutral value and label format are already set from the parameter.
+    pub fn with_knob(
+        mut self,
+        configure: impl for<'k> FnOnce(ParamKnobInner<'k>) -> ParamKnobInner<'k> + 'a,
+    ) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+impl<P: KnobParam> Widget for ParamKnob<'_, P> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        let param = self.param;
+        let mut new_value = None;
+        let mut set_value = |value: f32| new_value = Some(value);
+
+        let mut knob = Knob::new(
+            param.normalised(),
+            &mut set_value as &mut dyn FnMut(f32),
+            0.0..=1.0,
+            self.style,
+        )
+        .with_neutral(param.default_normalised())
+        .with_label_format(|value| param.display(value));
+        if let Some(steps) = param.step_count().filter(|&steps| steps > 0) {
+            knob = knob.with_step(1.0 / steps as f32);
+        }
+        if let Some(configure) = self.configure {
+            knob = configure(knob);
+        }
+        // Disabled and read-only knobs still report drags, but never change the value, so they
+        // mustn't open a gesture with the host.
+        let editable = knob.enabled && !knob.read_only && knob.learn.is_none() && ui.is_enabled();
+        let response = ui.add(knob);
+
+        if editable && response.drag_started() {
+            param.begin_gesture();
+        }
+        if let Some(value) = new_value {
+            let in_drag = response.dragged() || response.drag_stopped();
+            if !in_drag {
+                param.begin_gesture();
+            }
+            param.set_normalised(value);
+            if !in_drag {
+                param.end_gesture();
+            }
+        }
+        if editable && response.drag_stopped() {
+            param.end_gesture();
+        }
+
+        response
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{Knob, KnobVisuals, LabelPosition};
+use egui::{Response, Ui, Widget};
+use std::ops::RangeInclusive;
+
+type OnChange<'a> = Box<dyn FnMut(PidGains) + 'a>;
+
+/// Proportional, integral and derivative gains, as edited by [`PidKnobs`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PidGains {
+    pub p: f32,
+    pub i: f32,
+    pub d: f32,
+}
+
+impl Default for PidGains {
+    fn default() -> Self {
+        Self {
+            p: 1.0,
+            i: 0.1,
+            d: 0.01,
+        }
+    }
+}
+
+/// Three linked knobs for the gains of a PID controller, as in motor control and drone tuning
+/// tools.
+///
+/// The knobs share their visuals and use logarithmic ranges, since gains are tuned by orders of
+/// magnitude. Double clicking a knob resets its gain, and the reset button resets all three.
+/// Changes are reported once per frame with all the gains.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{PidGains, PidKnobs};
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut gains = PidGains::default();
+/// ui.add(PidKnobs::new(gains, |new_gains| gains = new_gains).with_size(40.0));
+/// # });
+/// # });
+/// ```
+pub struct PidKnobs<'a> {
+    gains: PidGains,
+    on_change: OnChange<'a>,
+    defaults: PidGains,
+    ranges: [RangeInclusive<f32>; 3],
+    visuals: KnobVisuals,
+    size: f32,
+}
+
+impl<'a> PidKnobs<'a> {
+    pub fn new(gains: PidGains, on_change: impl FnMut(PidGains) + 'a) -> Self {
+        Self {
+            gains,
+            on_change: Box::new(on_change),
+            defaults: PidGains::default(),
+            ranges: [0.01..=100.0, 0.001..=10.0, 0.0001..=1.0],
+            visuals: KnobVisuals::default(),
+            size: 40.0,
+        }
+    }
+
+    /// Sets the gains the knobs reset to.
+    pub fn with_defaults(mut self, defaults: PidGains) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
+    /// Sets the range of each gain. They should be positive, for the logarithmic mapping.
+    pub fn with_ranges(
+        mut self,
+        p: RangeInclusive<f32>,
+        i: RangeInclusive<f32>,
+        d: RangeInclusive<f32>,
+    ) -> Self {
+        self.ranges = [p, i, d];
+        self
+    }
+
+    /// Sets the colors and style of all three knobs.
+    pub fn with_visuals(mut self, visuals: KnobVisuals) -> Self {
+        self.visuals = visuals;
+        self
+    }
+
+    /// Sets the size of each knob.
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+}
+
+impl Widget for PidKnobs<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let mut gains = self.gains;
+        let defaults = [self.defaults.p, self.defaults.i, self.defaults.d];
+        let text_color = ui.visuals().text_color();
+
+        let mut response = ui
+            .horizontal(|ui| {
+                let mut values = [gains.p, gains.i, gains.d];
+                let mut response: Option<Response> = None;
+                for (((value, range), default), label) in values
+                    .iter_mut()
+                    .zip(self.ranges.clone())
+                    .zip(defaults)
+                    .zip(["P", "I", "D"])
+                {
+                    let knob = Knob::new(*value, |v| *value = v, range, self.visuals.style)
+                        .logarithmic(true)
+                        .with_neutral(default)
+                        .with_size(self.size)
+                        .with_visuals(&self.visuals)
+                        .with_text_color(text_color)
+                        .with_label(label, LabelPosition::Bottom);
+                    let knob_response = ui.add(knob);
+                    response = Some(match response {
+                        Some(response) => response | knob_response,
+                        None => knob_response,
+                    });
+                }
+                let reset = ui.small_button("Reset").on_hover_text("Reset all gains");
+                if reset.clicked() {
+                    values = defaults;
+                }
+                [gains.p, gains.i, gains.d] = values;
+                response.map_or(reset.clone(), |response| response | reset)
+            })
+            .inner;
+
+        if gains != self.gains {
+            (self.on_change)(gains);
+            response.mark_changed();
+        }
+        response
+    }
+}
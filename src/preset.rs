@@ -0,0 +1,135 @@
+use crate::normalise::same_position;
+use crate::state;
+use egui::{Context, Id};
+use std::time::Duration;
+
+/// A knob whose value differs from a [`KnobPreset`], see [`KnobPreset::diff`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PresetDifference {
+    pub id: Id,
+    /// The knob's current normalised value, or `None` if it hasn't been shown yet
+    pub current: Option<f32>,
+    /// The normalised value stored in the preset
+    pub preset: f32,
+}
+
+/// Named normalised values for a set of knobs, e.g. a synth patch.
+///
+/// Knobs are identified by the id given to [`crate::Knob::with_id`]. Values are kept in the
+/// knobs' normalised space, so applying a preset goes through each knob's taper and step, and
+/// reports the result through its setter. With the `serde` feature, presets can be serialized.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Knob, KnobPreset, KnobStyle};
+/// # use std::time::Duration;
+/// let ctx = egui::Context::default();
+/// let mut value = 2.0;
+/// let mut show = |value: &mut f32| {
+///     let _ = ctx.run(Default::default(), |ctx| {
+///         egui::CentralPanel::default().show(ctx, |ui| {
+///             ui.add(Knob::new(*value, |v| *value = v, 0.0..=10.0, KnobStyle::Wiper).with_id("gain"));
+///         });
+///     });
+/// };
+///
+/// show(&mut value);
+/// let preset = KnobPreset::capture(&ctx, "Init", ["gain"]);
+/// value = 7.0;
+/// show(&mut value);
+/// assert_eq!(preset.diff(&ctx).len(), 1);
+///
+/// preset.apply(&ctx, Duration::ZERO);
+/// show(&mut value);
+/// assert_eq!(value, 2.0);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct KnobPreset {
+    pub name: String,
+    values: Vec<(Id, f32)>,
+}
+
+impl KnobPreset {
+    /// An empty preset.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            values: Vec::new(),
+        }
+    }
+
+    /// A preset with the current values of the knobs with `ids`.
+    ///
+    /// Knobs that haven't been shown yet are left out.
+    pub fn capture<I: Into<Id>>(
+        ctx: &Context,
+        name: impl Into<String>,
+        ids: impl IntoIterator<Item = I>,
+    ) -> Self {
+        let mut preset = Self::new(name);
+        for id in ids {
+            let id = id.into();
+            if let Some(position) = state::last_position(ctx, id) {
+                preset.set(id, position);
+            }
+        }
+        preset
+    }
+
+    /// Sets the normalised value of the knob with `id`.
+    pub fn set(&mut self, id: impl Into<Id>, normalised: f32) {
+        let id = id.into();
+        let normalised = normalised.clamp(0.0, 1.0);
+        match self.values.iter_mut().find(|(knob, _)| *knob == id) {
+            Some((_, value)) => *value = normalised,
+            None => self.values.push((id, normalised)),
+        }
+    }
+
+    /// The normalised value of the knob with `id`, if it's part of the preset.
+    pub fn get(&self, id: impl Into<Id>) -> Option<f32> {
+        let id = id.into();
+        self.values
+            .iter()
+            .find_map(|(knob, value)| (*knob == id).then_some(*value))
+    }
+
+    /// Removes the knob with `id` from the preset.
+    pub fn remove(&mut self, id: impl Into<Id>) {
+        let id = id.into();
+        self.values.retain(|(knob, _)| *knob != id);
+    }
+
+    /// The knobs in the preset and their normalised values, in the order they were added.
+    pub fn values(&self) -> impl Iterator<Item = (Id, f32)> + '_ {
+        self.values.iter().copied()
+    }
+
+    /// Moves every knob in the preset to its value on the knob's next frame.
+    ///
+    /// With a nonzero `morph`, knobs move there over that time, reporting the values in
+    /// between. Morphs are skipped with reduced motion.
+    pub fn apply(&self, ctx: &Context, morph: Duration) {
+        for &(id, value) in &self.values {
+            state::queue_normalised(ctx, id, value, morph);
+        }
+    }
+
+    /// The knobs whose current value differs from the preset by more than a ten-thousandth of
+    /// their range.
+    pub fn diff(&self, ctx: &Context) -> Vec<PresetDifference> {
+        self.values
+            .iter()
+            .filter_map(|&(id, preset)| {
+                let current = state::last_position(ctx, id);
+                let matches = current.is_some_and(|current| same_position(current, preset));
+                (!matches).then_some(PresetDifference {
+                    id,
+                    current,
+                    preset,
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,383 @@
+use egui::epaint::{Mesh, Vertex, WHITE_UV};
+use egui::{Color32, Context, Id, Pos2, Vec2};
+use std::f32::consts::TAU;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+
+/// How the knob's outer ring is colored.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RingColor {
+    /// A single color: the knob color, or the dragging color while dragged.
+    Solid,
+    /// Blends from the first color at the minimum position to the second color at the maximum
+    /// position. The part of the ring outside the range of motion blends back again.
+    Gradient(Color32, Color32),
+    /// Uses `below` for the part of the ring before `value` and `above` for the part after it.
+    Threshold {
+        value: f32,
+        below: Color32,
+        above: Color32,
+    },
+    /// Safe, warning and danger zones, e.g. for gauges: `safe` up to the `warn` value,
+    /// `caution` up to the `danger` value, and `critical` after it.
+    Zones {
+        warn: f32,
+        danger: f32,
+        safe: Color32,
+        caution: Color32,
+        critical: Color32,
+    },
+}
+
+/// How finely ring arcs are split into segments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ArcQuality {
+    /// Segments roughly 4 physical pixels long, based on the radius and `pixels_per_point`.
+    Auto,
+    /// A fixed number of segments per radian. Lower is faster, higher is smoother.
+    SegmentsPerRadian(f32),
+}
+
+impl ArcQuality {
+    /// Segments for a full turn of a ring of `radius` points.
+    pub(crate) fn segments_per_turn(self, radius: f32, pixels_per_point: f32) -> f32 {
+        match self {
+            ArcQuality::Auto => TAU * radius * pixels_per_point / 4.0,
+            ArcQuality::SegmentsPerRadian(segments) => segments * TAU,
+        }
+    }
+}
+
+impl RingColor {
+    /// Applies `f` to every color in the ring.
+    pub(crate) fn map(self, f: impl Fn(Color32) -> Color32) -> Self {
+        match self {
+            RingColor::Solid => RingColor::Solid,
+            RingColor::Gradient(start, end) => RingColor::Gradient(f(start), f(end)),
+            RingColor::Threshold {
+                value,
+                below,
+                above,
+            } => RingColor::Threshold {
+                value,
+                below: f(below),
+                above: f(above),
+            },
+            RingColor::Zones {
+                warn,
+                danger,
+                safe,
+                caution,
+                critical,
+            } => RingColor::Zones {
+                warn,
+                danger,
+                safe: f(safe),
+                caution: f(caution),
+                critical: f(critical),
+            },
+        }
+    }
+}
+
+/// Adds a ring segment to `mesh`, from `start_turn` to `end_turn` (in fractions of a full
+/// rotation, 0.0 pointing right), blending from `start_color` to `end_color`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn add_arc(
+    mesh: &mut Mesh,
+    center: Pos2,
+    radius: f32,
+    width: f32,
+    segments_per_turn: f32,
+    start_turn: f32,
+    end_turn: f32,
+    start_color: Color32,
+    end_color: Color32,
+) {
+    add_tapered_arc(
+        mesh,
+        center,
+        radius,
+        [width; 2],
+        segments_per_turn,
+        start_turn,
+        end_turn,
+        start_color,
+        end_color,
+    );
+}
+
+/// Same as [`add_arc`], but the width changes linearly from `widths[0]` at `start_turn` to
+/// `widths[1]` at `end_turn`.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn add_tapered_arc(
+    mesh: &mut Mesh,
+    center: Pos2,
+    radius: f32,
+    widths: [f32; 2],
+    segments_per_turn: f32,
+    start_turn: f32,
+    end_turn: f32,
+    start_color: Color32,
+    end_color: Color32,
+) {
+    let span = (end_turn - start_turn).abs();
+    if span <= 0.0 {
+        return;
+    }
+
+    // A floor so that small arcs still look round.
+    let segments = (segments_per_turn * span).ceil().max(4.0) as u32;
+
+    let base = mesh.vertices.len() as u32;
+    for i in 0..=segments {
+        let t = i as f32 / segments as f32;
+        let dir = Vec2::angled(TAU * egui::lerp(start_turn..=end_turn, t));
+        let color = start_color.lerp_to_gamma(end_color, t);
+        let half_width = egui::lerp(widths[0]..=widths[1], t) * 0.5;
+        mesh.vertices.push(Vertex {
+            pos: center + dir * (radius - half_width),
+            uv: WHITE_UV,
+            color,
+        });
+        mesh.vertices.push(Vertex {
+            pos: center + dir * (radius + half_width),
+            uv: WHITE_UV,
+            color,
+        });
+    }
+    for i in 0..segments {
+        let idx = base + i * 2;
+        mesh.add_triangle(idx, idx + 1, idx + 2);
+        mesh.add_triangle(idx + 1, idx + 3, idx + 2);
+    }
+}
+
+/// Builds the mesh for a gradient, threshold or zone ring. Solid rings are stroked directly.
+///
+/// `start_turn` is where the range of motion begins and `motion` is its length, both in
+/// fractions of a full rotation. `thresholds` are the normalised positions of the threshold for
+/// [`RingColor::Threshold`], or of the zone boundaries for [`RingColor::Zones`].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn ring_mesh(
+    ring: RingColor,
+    center: Pos2,
+    radius: f32,
+    width: f32,
+    segments_per_turn: f32,
+    start_turn: f32,
+    motion: f32,
+    thresholds: [f32; 2],
+    solid: Color32,
+) -> Mesh {
+    let mut mesh = Mesh::default();
+    let end_turn = start_turn + motion;
+    let (first, last) = match ring {
+        RingColor::Solid => {
+            add_arc(
+                &mut mesh,
+                center,
+                radius,
+                width,
+                segments_per_turn,
+                start_turn,
+                start_turn + 1.0,
+                solid,
+                solid,
+            );
+            return mesh;
+        }
+        RingColor::Gradient(start, end) => {
+            add_arc(
+                &mut mesh,
+                center,
+                radius,
+                width,
+                segments_per_turn,
+                start_turn,
+                end_turn,
+                start,
+                end,
+            );
+            (start, end)
+        }
+        RingColor::Threshold { below, above, .. } => {
+            let split = start_turn + motion * thresholds[0].clamp(0.0, 1.0);
+            add_arc(
+                &mut mesh,
+                center,
+                radius,
+                width,
+                segments_per_turn,
+                start_turn,
+                split,
+                below,
+                below,
+            );
+            add_arc(
+                &mut mesh,
+                center,
+                radius,
+                width,
+                segments_per_turn,
+                split,
+                end_turn,
+                above,
+                above,
+            );
+            (below, above)
+        }
+        RingColor::Zones {
+            safe,
+            caution,
+            critical,
+            ..
+        } => {
+            let warn = start_turn + motion * thresholds[0].clamp(0.0, 1.0);
+            let danger = start_turn + motion * thresholds[1].clamp(0.0, 1.0);
+            for (from, to, color) in [
+                (start_turn, warn, safe),
+                (warn, danger.max(warn), caution),
+                (danger.max(warn), end_turn, critical),
+            ] {
+                add_arc(
+                    &mut mesh,
+                    center,
+                    radius,
+                    width,
+                    segments_per_turn,
+                    from,
+                    to,
+                    color,
+                    color,
+                );
+            }
+            (safe, critical)
+        }
+    };
+
+    // The gap below the knob, outside the range of motion, blends back to the start.
+    add_arc(
+        &mut mesh,
+        center,
+        radius,
+        width,
+        segments_per_turn,
+        end_turn,
+        start_turn + 1.0,
+        last,
+        first,
+    );
+    mesh
+}
+
+/// The ring mesh last built for a knob, with a hash of everything it was built from.
+#[derive(Clone)]
+struct CachedRing {
+    key: u64,
+    /// The ring around the origin.
+    mesh: Arc<Mesh>,
+    /// The ring moved to where it was last drawn.
+    placed: Arc<Mesh>,
+    center: Pos2,
+}
+
+/// Same as [`ring_mesh`], but reuses the mesh from the previous frame of knob `id` when none of
+/// the inputs have changed.
+///
+/// The mesh is built around the origin and moved to `center`, so a knob that moves, e.g. in a
+/// scrolling panel, only has its vertices offset rather than the ring rebuilt.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn cached_ring_mesh(
+    ctx: &Context,
+    id: Id,
+    ring: RingColor,
+    center: Pos2,
+    radius: f32,
+    width: f32,
+    segments_per_turn: f32,
+    start_turn: f32,
+    motion: f32,
+    thresholds: [f32; 2],
+    solid: Color32,
+) -> Arc<Mesh> {
+    let mut hasher = DefaultHasher::new();
+    for x in [
+        radius,
+        width,
+        segments_per_turn,
+        start_turn,
+        motion,
+        thresholds[0],
+        thresholds[1],
+    ] {
+        x.to_bits().hash(&mut hasher);
+    }
+    solid.hash(&mut hasher);
+    match ring {
+        RingColor::Solid => 0u8.hash(&mut hasher),
+        RingColor::Gradient(start, end) => (1u8, start, end).hash(&mut hasher),
+        RingColor::Threshold {
+            value,
+            below,
+            above,
+        } => (2u8, value.to_bits(), below, above).hash(&mut hasher),
+        RingColor::Zones {
+            warn,
+            danger,
+            safe,
+            caution,
+            critical,
+        } => (
+            3u8,
+            warn.to_bits(),
+            danger.to_bits(),
+            safe,
+            caution,
+            critical,
+        )
+            .hash(&mut hasher),
+    }
+    let key = hasher.finish();
+
+    let cached = ctx
+        .data(|data| data.get_temp::<CachedRing>(id))
+        .filter(|cached| cached.key == key);
+    if let Some(cached) = &cached
+        && cached.center == center
+    {
+        return cached.placed.clone();
+    }
+
+    let mesh = cached.map_or_else(
+        || {
+            Arc::new(ring_mesh(
+                ring,
+                Pos2::ZERO,
+                radius,
+                width,
+                segments_per_turn,
+                start_turn,
+                motion,
+                thresholds,
+                solid,
+            ))
+        },
+        |cached| cached.mesh,
+    );
+    let mut placed = (*mesh).clone();
+    placed.translate(center.to_vec2());
+    let placed = Arc::new(placed);
+    ctx.data_mut(|data| {
+        data.insert_temp(
+            id,
+            CachedRing {
+                key,
+                mesh,
+                placed: placed.clone(),
+                center,
+            },
+        )
+    });
+    placed
+}
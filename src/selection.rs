@@ -0,0 +1,176 @@
+use egui::{Context, Id, Modifiers};
+
+use crate::state;
+use crate::{GroupMode, KnobGroup};
+
+/// Selected knobs that are edited together, e.g. the channels of a mixer.
+///
+/// Clicking a knob selects it alone, and clicking it with command (ctrl) held adds it to or
+/// removes it from the selection. Dragging a selected knob moves every selected knob, and
+/// pressing delete or backspace over one resets them all to their neutral values. Knobs join
+/// with [`crate::Knob::with_selection`], and need an id from [`crate::Knob::with_id`]. The
+/// selection is kept in egui memory.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::{Knob, KnobSelection, KnobStyle};
+/// # let ctx = egui::Context::default();
+/// let selection = KnobSelection::new("mixer");
+/// selection.set_selected(&ctx, ["volume 1", "volume 2"]);
+/// assert!(selection.is_selected(&ctx, "volume 2"));
+///
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut volumes = [0.5, 0.8, 0.2];
+/// for (volume, id) in volumes.iter_mut().zip(["volume 1", "volume 2", "volume 3"]) {
+///     ui.add(
+///         Knob::new(*volume, |v| *volume = v, 0.0..=1.0, KnobStyle::Wiper)
+///             .with_id(id)
+///             .with_neutral(0.5)
+///             .with_selection(&selection),
+///     );
+/// }
+/// # });
+/// # });
+/// ```
+#[derive(Clone, Debug)]
+pub struct KnobSelection {
+    id: Id,
+    mode: GroupMode,
+}
+
+impl KnobSelection {
+    /// A selection with a unique `id`.
+    pub fn new(id: impl Into<Id>) -> Self {
+        Self {
+            id: id.into(),
+            mode: GroupMode::Relative,
+        }
+    }
+
+    /// Sets how the other selected knobs follow the dragged one. The default is
+    /// [`GroupMode::Relative`].
+    pub fn with_mode(mut self, mode: GroupMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    fn selected_id(&self) -> Id {
+        self.id.with("selected")
+    }
+
+    /// The ids of the selected knobs, in the order they were selected.
+    pub fn selected(&self, ctx: &Context) -> Vec<Id> {
+        ctx.data(|data| data.get_temp(self.selected_id()))
+            .unwrap_or_default()
+    }
+
+    /// Whether the knob with `id` is selected.
+    pub fn is_selected(&self, ctx: &Context, id: impl Into<Id>) -> bool {
+        self.selected(ctx).contains(&id.into())
+    }
+
+    /// Selects exactly the knobs with `ids`.
+    pub fn set_selected<I: Into<Id>>(&self, ctx: &Context, ids: impl IntoIterator<Item = I>) {
+        let selected: Vec<Id> = ids.into_iter().map(Into::into).collect();
+        ctx.data_mut(|data| data.insert_temp(self.selected_id(), selected));
+    }
+
+    /// Deselects every knob.
+    pub fn clear(&self, ctx: &Context) {
+        self.set_selected::<Id>(ctx, []);
+    }
+
+    /// Selects the clicked knob with `id`, or toggles it with `toggle`.
+    pub(crate) fn click(&self, ctx: &Context, id: Id, toggle: bool) {
+        let mut selected = self.selected(ctx);
+        if !toggle {
+            selected = vec![id];
+        } else if let Some(index) = selected.iter().position(|&selected| selected == id) {
+            selected.remove(index);
+        } else {
+            selected.push(id);
+        }
+        self.set_selected(ctx, selected);
+    }
+
+    /// The selected knobs as a group that moves together without any modifiers.
+    pub(crate) fn group(&self, ctx: &Context) -> KnobGroup {
+        KnobGroup::new(self.id, self.selected(ctx))
+            .with_modifiers(Modifiers::NONE)
+            .with_mode(self.mode)
+    }
+
+    /// Resets every selected knob to its neutral value on its next frame.
+    pub(crate) fn reset(&self, ctx: &Context) {
+        for id in self.selected(ctx) {
+            state::request_reset(ctx, id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::testing::KnobHarness;
+    use crate::{Knob, KnobStyle};
+    use egui::{Event, Key, Response, Ui};
+
+    const IDS: [&str; 3] = ["volume 1", "volume 2", "volume 3"];
+
+    /// Shows a knob for each value, returning the first.
+    fn show_mixer(ui: &mut Ui, values: &mut [f32; 3], selection: &KnobSelection) -> Response {
+        let responses: Vec<_> = values
+            .iter_mut()
+            .zip(IDS)
+            .map(|(volume, id)| {
+                ui.add(
+                    Knob::new(*volume, |v| *volume = v, 0.0..=1.0, KnobStyle::Wiper)
+                        .with_id(id)
+                        .with_neutral(0.5)
+                        .with_selection(selection),
+                )
+            })
+            .collect();
+        responses[0].clone()
+    }
+
+    #[test]
+    fn dragging_moves_every_selected_knob() {
+        let selection = KnobSelection::new("mixer");
+        let mut values = [0.2, 0.4, 0.6];
+        let mut harness = KnobHarness::new();
+        selection.set_selected(harness.ctx(), [IDS[0], IDS[1]]);
+        harness.drag(-40.0, |ui| show_mixer(ui, &mut values, &selection));
+        harness.idle(2, |ui| show_mixer(ui, &mut values, &selection));
+        let moved = values[0] - 0.2;
+        assert!(moved > 0.0);
+        assert!((values[1] - (0.4 + moved)).abs() < 1e-5, "{values:?}");
+        assert_eq!(values[2], 0.6);
+    }
+
+    #[test]
+    fn delete_resets_every_selected_knob() {
+        let selection = KnobSelection::new("mixer");
+        let mut values = [0.2, 0.4, 0.6];
+        let mut harness = KnobHarness::new();
+        selection.set_selected(harness.ctx(), [IDS[0], IDS[2]]);
+        let pos = harness
+            .idle(1, |ui| show_mixer(ui, &mut values, &selection))
+            .rect
+            .center();
+        harness.run(vec![Event::PointerMoved(pos)], |ui| {
+            show_mixer(ui, &mut values, &selection)
+        });
+        let delete = Event::Key {
+            key: Key::Delete,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: Modifiers::NONE,
+        };
+        harness.run(vec![delete], |ui| show_mixer(ui, &mut values, &selection));
+        harness.idle(2, |ui| show_mixer(ui, &mut values, &selection));
+        assert_eq!(values, [0.5, 0.4, 0.5]);
+    }
+}
@@ -0,0 +1,209 @@
+use crate::KnobGeometry;
+use crate::animation::{Sweep, ValueSweep};
+use egui::{Context, Id};
+use std::time::Duration;
+
+/// Hold-to-repeat nudging in progress.
+#[derive(Clone, Copy)]
+pub(crate) struct Nudge {
+    /// `1.0` to increment, `-1.0` to decrement.
+    pub direction: f32,
+    /// When the next step is due.
+    pub next_time: f64,
+    /// Number of steps made since the press.
+    pub repeats: i32,
+}
+
+/// A change waiting for confirmation, see [`crate::Knob::with_confirm`].
+#[derive(Clone, Copy)]
+pub(crate) struct Staged {
+    pub value: f32,
+    /// When the staged value last changed.
+    pub time: f64,
+}
+
+/// Everything a knob remembers between frames, stored in egui memory under the knob's [`Id`].
+///
+/// Copied out of memory and back every frame, so anything owning heap memory goes in
+/// [`KnobBuffers`] instead.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct KnobState {
+    /// Normalised position when the current drag started.
+    pub drag_start: Option<f32>,
+    /// Value when the current drag started.
+    pub drag_start_value: Option<f32>,
+    /// Vertical drag distance since the drag started, after fine-drag scaling.
+    pub drag_distance: f32,
+    /// Whether the current drag carries the value to another knob instead of changing it.
+    pub carrying: bool,
+    /// The value on the previous frame, to detect changes made from outside the knob.
+    pub last_value: Option<f32>,
+    /// When the change flash was last triggered.
+    pub flash_start: Option<f64>,
+    /// Transition of the displayed position.
+    pub position_sweep: Option<Sweep>,
+    /// Normalised position on the previous frame, for capturing presets.
+    pub last_position: Option<f32>,
+    /// Where the knob was drawn on the previous frame, see [`crate::KnobHandle::geometry`].
+    pub last_geometry: Option<KnobGeometry>,
+    /// Hold-to-repeat nudge while the pointer is held on the knob.
+    pub nudge: Option<Nudge>,
+    /// Whether a click latched the knob to the pointer, see [`crate::Knob::latch`].
+    pub latched: bool,
+    /// Whether the current drag moves the knob's [`crate::KnobGroup`] too.
+    pub group_drag: bool,
+    /// How far, in normalised units, the current drag pushes past either end of the range:
+    /// negative below the start, positive above the end.
+    pub overshoot: f32,
+    /// Whether the current drag uses the fine step, see [`crate::Knob::with_fine_step`].
+    pub fine_step: bool,
+    /// Whether the magnified overlay from a long press is open.
+    pub magnified: bool,
+    /// Latest stylus pressure of the current drag, for frames without touch events.
+    pub pen_pressure: Option<f32>,
+    /// Value before alt-clicking to neutral, restored by alt-clicking again.
+    pub flipped_from: Option<f32>,
+    /// Change waiting for confirmation.
+    pub staged: Option<Staged>,
+    /// Unsnapped position while the analog stick moves the knob, so slow moves reach the next step.
+    pub stick_position: Option<f32>,
+    /// Sweep that reports intermediate values, from a reset or a preset morph.
+    pub value_sweep: Option<ValueSweep>,
+}
+
+impl KnobState {
+    pub(crate) fn load(ctx: &Context, id: Id) -> Self {
+        ctx.data(|data| data.get_temp(id)).unwrap_or_default()
+    }
+
+    pub(crate) fn store(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|data| data.insert_temp(id, self));
+    }
+
+    /// The id the state of the knob with `knob_id` is stored under.
+    pub(crate) fn id(knob_id: Id) -> Id {
+        knob_id.with("state")
+    }
+}
+
+/// The parts of a knob's state that own heap memory, kept apart from [`KnobState`].
+///
+/// Moved out of memory and back each frame rather than cloned.
+#[derive(Clone, Default)]
+pub(crate) struct KnobBuffers {
+    /// `(time, value)` pairs of the current drag, when recording gestures.
+    pub gesture: Option<Vec<(f64, f32)>>,
+    /// Text of the typed value entry, while it's open.
+    pub editing: Option<String>,
+}
+
+impl KnobBuffers {
+    pub(crate) fn take(ctx: &Context, id: Id) -> Self {
+        ctx.data_mut(|data| std::mem::take(data.get_temp_mut_or_default::<Self>(id)))
+    }
+
+    pub(crate) fn put_back(self, ctx: &Context, id: Id) {
+        ctx.data_mut(|data| *data.get_temp_mut_or_default::<Self>(id) = self);
+    }
+}
+
+/// The normalised position the knob with `knob_id` showed on its last frame, if it has been
+/// shown.
+pub(crate) fn last_position(ctx: &Context, knob_id: Id) -> Option<f32> {
+    ctx.data(|data| data.get_temp::<KnobState>(KnobState::id(knob_id)))
+        .and_then(|state| state.last_position)
+}
+
+/// Where the knob with `knob_id` was drawn on its last frame.
+pub(crate) fn last_geometry(ctx: &Context, knob_id: Id) -> Option<KnobGeometry> {
+    ctx.data(|data| data.get_temp::<KnobState>(KnobState::id(knob_id)))
+        .and_then(|state| state.last_geometry)
+}
+
+fn queued_id(knob_id: Id) -> Id {
+    knob_id.with("queued_normalised")
+}
+
+/// A normalised value for a knob to move to on its next frame.
+#[derive(Clone, Copy, Default)]
+pub(crate) struct Queued {
+    pub normalised: f32,
+    /// How long the knob takes to get there, reporting the values in between.
+    pub morph: Duration,
+    /// Where to record the position the knob lands on, when a [`crate::KnobGroup`] queued it.
+    pub group_changes: Option<Id>,
+}
+
+/// Queues a normalised value for the knob with `knob_id` to take on its next frame.
+///
+/// This lives outside [`KnobState`] so it can be called from other threads without being
+/// overwritten when the knob stores its state at the end of a frame.
+pub(crate) fn queue_normalised(ctx: &Context, knob_id: Id, normalised: f32, morph: Duration) {
+    queue(
+        ctx,
+        knob_id,
+        Queued {
+            normalised,
+            morph,
+            group_changes: None,
+        },
+    );
+}
+
+/// Queues `queued` for the knob with `knob_id` to take on its next frame.
+pub(crate) fn queue(ctx: &Context, knob_id: Id, queued: Queued) {
+    ctx.data_mut(|data| data.insert_temp(queued_id(knob_id), queued));
+    ctx.request_repaint();
+}
+
+/// Takes the value queued with [`queue_normalised`], if any.
+pub(crate) fn take_queued(ctx: &Context, knob_id: Id) -> Option<Queued> {
+    ctx.data_mut(|data| data.remove_temp(queued_id(knob_id)))
+}
+
+fn queued_value_id(knob_id: Id) -> Id {
+    knob_id.with("queued_value")
+}
+
+/// Queues a value, in the knob's own units, for the knob with `knob_id` to take on its next
+/// frame.
+pub(crate) fn queue_value(ctx: &Context, knob_id: Id, value: f32) {
+    ctx.data_mut(|data| data.insert_temp(queued_value_id(knob_id), value));
+    ctx.request_repaint();
+}
+
+/// Takes the value queued with [`queue_value`], if any.
+pub(crate) fn take_queued_value(ctx: &Context, knob_id: Id) -> Option<f32> {
+    ctx.data_mut(|data| data.remove_temp(queued_value_id(knob_id)))
+}
+
+fn stick_id() -> Id {
+    Id::new("egui_fancy_knob_stick")
+}
+
+/// Stores the analog stick deflection that moves the focused knob.
+pub(crate) fn set_stick(ctx: &Context, deflection: f32) {
+    ctx.data_mut(|data| data.insert_temp(stick_id(), deflection));
+    ctx.request_repaint();
+}
+
+/// The analog stick deflection stored with [`set_stick`].
+pub(crate) fn stick(ctx: &Context) -> f32 {
+    ctx.data(|data| data.get_temp(stick_id())).unwrap_or(0.0)
+}
+
+fn reset_id(knob_id: Id) -> Id {
+    knob_id.with("reset_requested")
+}
+
+/// Asks the knob with `knob_id` to reset to its neutral value on its next frame.
+pub(crate) fn request_reset(ctx: &Context, knob_id: Id) {
+    ctx.data_mut(|data| data.insert_temp(reset_id(knob_id), true));
+    ctx.request_repaint();
+}
+
+/// Whether a reset was requested with [`request_reset`], clearing the request.
+pub(crate) fn take_reset(ctx: &Context, knob_id: Id) -> bool {
+    ctx.data_mut(|data| data.remove_temp::<bool>(reset_id(knob_id)))
+        .unwrap_or(false)
+}
@@ -0,0 +1,159 @@
+//! Helpers for driving knobs in a headless egui context, for integration tests.
+//!
+//! Only available with the `testing` feature.
+//!
+//! # Example
+//! ```
+//! use egui_fancy_knob::testing::KnobHarness;
+//! use egui_fancy_knob::{Knob, KnobStyle};
+//!
+//! let mut value = 0.0;
+//! let mut harness = KnobHarness::new();
+//! let mut show = |ui: &mut egui::Ui| {
+//!     ui.add(Knob::new(value, |v| value = v, 0.0..=1.0, KnobStyle::Wiper).with_neutral(0.25))
+//! };
+//!
+//! harness.drag(-40.0, &mut show);
+//! harness.double_click(&mut show);
+//! drop(show);
+//! assert_eq!(value, 0.25);
+//! ```
+
+use egui::{
+    CentralPanel, Context, Event, Modifiers, MouseWheelUnit, PointerButton, Pos2, RawInput, Rect,
+    Response, Ui, Vec2,
+};
+
+/// Time between simulated frames, in seconds.
+const FRAME_TIME: f64 = 1.0 / 60.0;
+
+/// Number of frames a simulated drag is spread over.
+const DRAG_FRAMES: usize = 10;
+
+/// A headless egui context that shows a single widget and feeds it simulated input.
+///
+/// Every method takes a `show` closure that adds the widget under test to the [`Ui`]. It is
+/// called once per simulated frame, so it should rebuild the knob from the current value each
+/// time, as an app would.
+pub struct KnobHarness {
+    ctx: Context,
+    time: f64,
+    modifiers: Modifiers,
+}
+
+impl Default for KnobHarness {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KnobHarness {
+    pub fn new() -> Self {
+        Self {
+            ctx: Context::default(),
+            time: 0.0,
+            modifiers: Modifiers::NONE,
+        }
+    }
+
+    /// The egui context the widget is shown in.
+    pub fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    /// Holds `modifiers` down during the following simulated input, e.g. to test fine dragging.
+    pub fn with_modifiers(mut self, modifiers: Modifiers) -> Self {
+        self.modifiers = modifiers;
+        self
+    }
+
+    /// Runs one frame with `events`, returning the widget's response.
+    pub fn run(
+        &mut self,
+        events: Vec<Event>,
+        mut show: impl FnMut(&mut Ui) -> Response,
+    ) -> Response {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(Pos2::ZERO, Vec2::new(800.0, 600.0))),
+            time: Some(self.time),
+            modifiers: self.modifiers,
+            events,
+            ..Default::default()
+        };
+        self.time += FRAME_TIME;
+
+        let mut response = None;
+        let _ = self.ctx.run(input, |ctx| {
+            CentralPanel::default().show(ctx, |ui| response = Some(show(ui)));
+        });
+        response.expect("`show` is called every frame")
+    }
+
+    /// Runs frames without input, e.g. to let animations finish.
+    pub fn idle(&mut self, frames: usize, mut show: impl FnMut(&mut Ui) -> Response) -> Response {
+        let mut response = self.run(Vec::new(), &mut show);
+        for _ in 1..frames {
+            response = self.run(Vec::new(), &mut show);
+        }
+        response
+    }
+
+    /// Presses on the middle of the widget, drags it `dy` points down (negative is up) over
+    /// several frames, and releases.
+    pub fn drag(&mut self, dy: f32, show: impl FnMut(&mut Ui) -> Response) -> Response {
+        self.drag_at(Vec2::ZERO, Vec2::new(0.0, dy), show)
+    }
+
+    /// Presses `offset` points from the middle of the widget, drags it by `delta` over several
+    /// frames, and releases.
+    pub fn drag_at(
+        &mut self,
+        offset: Vec2,
+        delta: Vec2,
+        mut show: impl FnMut(&mut Ui) -> Response,
+    ) -> Response {
+        let start = self.idle(1, &mut show).rect.center() + offset;
+        self.run(vec![Event::PointerMoved(start)], &mut show);
+        self.run(vec![self.button(start, true)], &mut show);
+
+        let mut pos = start;
+        for _ in 0..DRAG_FRAMES {
+            pos += delta / DRAG_FRAMES as f32;
+            self.run(vec![Event::PointerMoved(pos)], &mut show);
+        }
+        self.run(vec![self.button(pos, false)], &mut show)
+    }
+
+    /// Double clicks the middle of the widget.
+    pub fn double_click(&mut self, mut show: impl FnMut(&mut Ui) -> Response) -> Response {
+        let pos = self.idle(1, &mut show).rect.center();
+        self.run(vec![Event::PointerMoved(pos)], &mut show);
+        self.run(vec![self.button(pos, true)], &mut show);
+        self.run(vec![self.button(pos, false)], &mut show);
+        self.run(vec![self.button(pos, true)], &mut show);
+        self.run(vec![self.button(pos, false)], &mut show)
+    }
+
+    /// Scrolls the mouse wheel by `delta` points over the middle of the widget.
+    pub fn scroll(&mut self, delta: Vec2, mut show: impl FnMut(&mut Ui) -> Response) -> Response {
+        let pos = self.idle(1, &mut show).rect.center();
+        self.run(vec![Event::PointerMoved(pos)], &mut show);
+        self.run(
+            vec![Event::MouseWheel {
+                unit: MouseWheelUnit::Point,
+                delta,
+                modifiers: self.modifiers,
+            }],
+            &mut show,
+        )
+    }
+
+    fn button(&self, pos: Pos2, pressed: bool) -> Event {
+        Event::PointerButton {
+            pos,
+            button: PointerButton::Primary,
+            pressed,
+            modifiers: self.modifiers,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use egui::Ui;
+use std::ops::RangeInclusive;
+
+/// What a knob's tooltip can show, see [`crate::Knob::with_tooltip`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct KnobInfo {
+    pub value: f32,
+    /// The value's position in the range, from `0.0` to `1.0`, after the taper
+    pub normalised: f32,
+    /// The value as the label shows it
+    pub text: String,
+    pub range: RangeInclusive<f32>,
+    /// The value double clicking resets to, if any
+    pub neutral: Option<f32>,
+    /// Whether the value differs from the neutral value
+    pub modified: bool,
+}
+
+pub(crate) type Tooltip<'a> = Box<dyn FnOnce(&mut Ui, &KnobInfo) + 'a>;
@@ -0,0 +1,208 @@
+use crate::{Knob, KnobStyle};
+use egui::{Id, Label, Response, RichText, Sense, TextStyle, Ui, Widget};
+use std::ops::RangeInclusive;
+
+/// The knob a [`TuningKnob`] shows, as passed to [`TuningKnob::with_knob`].
+pub type TuningKnobInner<'k> = Knob<'k, &'k mut dyn FnMut(f32)>;
+
+type Configure<'a> = Box<dyn for<'k> FnOnce(TuningKnobInner<'k>) -> TuningKnobInner<'k> + 'a>;
+type SetFrequency<'a> = Box<dyn FnMut(f64) + 'a>;
+
+/// Detents per turn of the tuning knob, each moving the selected digit by one.
+const ENCODER_STEPS: f32 = 24.0;
+
+/// Which digit is selected and where the endless knob points, kept between frames.
+#[derive(Clone, Copy, Debug, Default)]
+struct TuningState {
+    digit: u32,
+    encoder: f32,
+}
+
+/// A radio tuning knob that changes one digit of a frequency at a time, as in software-defined
+/// radio UIs.
+///
+/// The frequency is shown next to the knob with the digit being tuned highlighted. Click a digit
+/// or scroll over the frequency to select another, then turn the knob or scroll over it to
+/// change that digit. Frequencies are in Hz, and the knob turns endlessly.
+///
+/// # Example
+/// ```
+/// # use egui_fancy_knob::TuningKnob;
+/// # let ctx = egui::Context::default();
+/// # let _ = ctx.run(Default::default(), |ctx| {
+/// # egui::CentralPanel::default().show(ctx, |ui| {
+/// let mut frequency = 145_500_000.0;
+/// ui.add(TuningKnob::new(frequency, |f| frequency = f, 0.0..=999_999_999.0));
+/// # });
+/// # });
+/// ```
+pub struct TuningKnob<'a> {
+    frequency: f64,
+    set_frequency: SetFrequency<'a>,
+    range: RangeInclusive<f64>,
+    digits: Option<u32>,
+    id: Option<Id>,
+    configure: Option<Configure<'a>>,
+}
+
+impl<'a> TuningKnob<'a> {
+    /// A tuning knob for `frequency`, in Hz, limited to `range`.
+    pub fn new(
+        frequency: f64,
+        set_frequency: impl FnMut(f64) + 'a,
+        range: RangeInclusive<f64>,
+    ) -> Self {
+        Self {
+            frequency,
+            set_frequency: Box::new(set_frequency),
+            range,
+            digits: None,
+            id: None,
+            configure: None,
+        }
+    }
+
+    /// Sets how many digits are shown. The default is enough for the end of the range.
+    pub fn with_digits(mut self, digits: u32) -> Self {
+        self.digits = Some(digits.max(1));
+        self
+    }
+
+    /// Sets the id the selected digit is remembered under, for tuning knobs whose position in
+    /// the layout changes.
+    pub fn with_id(mut self, id: impl Into<Id>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Customizes the underlying knob, e.g. its size, colors and label.
+    ///
+    /// The range, step and label format are already set; the label shows the size of a step.
+    pub fn with_knob(
+        mut self,
+        configure: impl for<'k> FnOnce(TuningKnobInner<'k>) -> TuningKnobInner<'k> + 'a,
+    ) -> Self {
+        self.configure = Some(Box::new(configure));
+        self
+    }
+}
+
+/// `hz` as text with a unit, e.g. `"10 kHz"`.
+fn step_text(hz: f64) -> String {
+    match hz {
+        hz if hz >= 1e9 => format!("{} GHz", hz / 1e9),
+        hz if hz >= 1e6 => format!("{} MHz", hz / 1e6),
+        hz if hz >= 1e3 => format!("{} kHz", hz / 1e3),
+        hz => format!("{hz} Hz"),
+    }
+}
+
+impl Widget for TuningKnob<'_> {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        let id = self.id.unwrap_or_else(|| ui.next_auto_id()).with("tuning");
+        let mut state = ui
+            .data(|data| data.get_temp::<TuningState>(id))
+            .unwrap_or_default();
+        let (low, high) = (
+            self.range.start().min(*self.range.end()),
+            self.range.start().max(*self.range.end()),
+        );
+        let digits = self.digits.unwrap_or_else(|| {
+            (high.abs().max(low.abs()).max(1.0).log10().floor() as u32 + 1).max(1)
+        });
+        state.digit = state.digit.min(digits - 1);
+        let mut steps = 0.0;
+
+        let response = ui
+            .horizontal(|ui| {
+                let mut new_encoder = None;
+                let mut set_encoder = |encoder: f32| new_encoder = Some(encoder);
+                let step_hz = 10f64.powi(state.digit as i32);
+                let mut knob = Knob::new(
+                    state.encoder,
+                    &mut set_encoder as &mut dyn FnMut(f32),
+                    0.0..=ENCODER_STEPS,
+                    KnobStyle::Dot,
+                )
+                .with_step(1.0)
+                .with_sweep(360.0)
+                .wrap(true)
+                .with_label_format(|_| step_text(step_hz));
+                if let Some(configure) = self.configure.take() {
+                    knob = configure(knob);
+                }
+                let mut response = ui.add(knob);
+                if let Some(encoder) = new_encoder {
+                    // The knob wraps around, so take the short way between positions.
+                    let mut delta = encoder - state.encoder;
+                    if delta > ENCODER_STEPS * 0.5 {
+                        delta -= ENCODER_STEPS;
+                    } else if delta < -ENCODER_STEPS * 0.5 {
+                        delta += ENCODER_STEPS;
+                    }
+                    steps += delta.round();
+                    state.encoder = encoder.rem_euclid(ENCODER_STEPS);
+                }
+                let scroll = ui.input(|input| input.raw_scroll_delta.y);
+                if response.hovered() && scroll != 0.0 {
+                    steps += scroll.signum();
+                }
+
+                let font_id = TextStyle::Monospace.resolve(ui.style());
+                let font_id = egui::FontId::new(font_id.size * 1.5, font_id.family);
+                let mut readout = None::<Response>;
+                ui.spacing_mut().item_spacing.x = 0.0;
+                for digit in (0..digits).rev() {
+                    let value = (self.frequency.abs() / 10f64.powi(digit as i32)).floor() % 10.0;
+                    let leading = self.frequency.abs() < 10f64.powi(digit as i32) && digit > 0;
+                    let mut text = RichText::new(format!("{value:.0}")).font(font_id.clone());
+                    text = if digit == state.digit {
+                        text.color(ui.visuals().strong_text_color())
+                            .background_color(ui.visuals().selection.bg_fill)
+                    } else if leading {
+                        text.weak()
+                    } else {
+                        text
+                    };
+                    let digit_response = ui.add(Label::new(text).sense(Sense::click()));
+                    if digit_response.clicked() {
+                        state.digit = digit;
+                    }
+                    readout = Some(match readout {
+                        Some(readout) => readout | digit_response,
+                        None => digit_response,
+                    });
+                    if digit > 0 && digit % 3 == 0 {
+                        ui.label(RichText::new(".").font(font_id.clone()).weak());
+                    }
+                }
+                ui.label(RichText::new(" Hz").font(font_id));
+                if let Some(readout) = readout {
+                    if readout.hovered() {
+                        let scroll = ui.input(|input| input.raw_scroll_delta.y);
+                        if scroll > 0.0 {
+                            state.digit = (state.digit + 1).min(digits - 1);
+                        } else if scroll < 0.0 {
+                            state.digit = state.digit.saturating_sub(1);
+                        }
+                    }
+                    response |= readout;
+                }
+                response
+            })
+            .inner;
+
+        let mut response = response;
+        if steps != 0.0 {
+            let frequency = (self.frequency + steps as f64 * 10f64.powi(state.digit as i32))
+                .round()
+                .clamp(low, high);
+            if frequency != self.frequency {
+                (self.set_frequency)(frequency);
+                response.mark_changed();
+            }
+        }
+        ui.data_mut(|data| data.insert_temp(id, state));
+        response
+    }
+}